@@ -17,17 +17,11 @@ fn main() {
         .opt_str("p")
         .unwrap_or_else(|| DEFAULT_DEVICE_FILE_PATH.to_owned());
 
-    let mut port = Port::try_new(path, BAUD_RATE, TIMEOUT).unwrap();
-
-    let command = Command::PositionRequest;
+    let mut encoder = Encoder::try_new(path, BAUD_RATE, TIMEOUT, CounterType::SingleTurn).unwrap();
 
     loop {
-        port.send_command(&command).expect("failed to send");
-
-        let mut position = Position::new(CounterType::SingleTurn);
-        port.receive(&mut position).expect("failed to receive");
+        let position = encoder.read_position().expect("failed to read position");
 
-        assert!(position.is_valid_prefix());
         assert!(!position.is_error());
         assert!(!position.is_warning());
 
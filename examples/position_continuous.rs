@@ -21,26 +21,14 @@ fn main() {
         .opt_str("c")
         .map_or_else(|| DEFAULT_CYCLE_TIME_MICROS, |s| s.parse().unwrap());
 
-    let mut port = Port::try_new(path, BAUD_RATE, TIMEOUT).unwrap();
-
-    port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
-        .unwrap();
-
-    port.send_programming_command(&ProgrammingCommand::ContinuousResponseSetting {
-        auto_start: false,
-        command: Command::PositionRequest,
-        period_micros: cycle_time,
-    })
-    .unwrap();
-
-    port.send_programming_command(&ProgrammingCommand::ContinuousResponseStart)
+    let encoder = Encoder::try_new(path, BAUD_RATE, TIMEOUT, CounterType::SingleTurn).unwrap();
+    let stream = encoder
+        .into_continuous_stream::<Position>(cycle_time)
         .unwrap();
 
-    loop {
-        let mut position = Position::new(CounterType::SingleTurn);
-        port.receive(&mut position).expect("failed to receive");
+    for sample in stream.iter() {
+        let position = sample.expect("failed to receive").response;
 
-        assert!(position.is_valid_prefix());
         assert!(!position.is_error());
         assert!(!position.is_warning());
 
@@ -0,0 +1,234 @@
+use std::f64::consts::PI;
+
+use crate::{
+    async_serial::PositionAndStatus, error::Result, turn_tracker::wrapping_delta, Resolution,
+    TurnTracker,
+};
+
+const MULTITURN_COUNTER_RANGE: i64 = 1 << 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbsolutePosition {
+    counts: i64,
+    resolution: Resolution,
+}
+
+impl AbsolutePosition {
+    pub fn new(counts: i64, resolution: Resolution) -> Self {
+        Self { counts, resolution }
+    }
+
+    pub fn from_response(response: &(impl PositionAndStatus + ?Sized)) -> Self {
+        let counts_per_revolution = response.resolution().counts_per_revolution() as i64;
+        Self::new(
+            i64::from(response.multiturn_count().unwrap_or(0)) * counts_per_revolution
+                + i64::from(response.position()),
+            response.resolution(),
+        )
+    }
+
+    pub fn counts(&self) -> i64 {
+        self.counts
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    fn counts_per_revolution(&self) -> i64 {
+        self.resolution.counts_per_revolution() as i64
+    }
+
+    pub fn turns(&self) -> i64 {
+        (self.counts + self.counts_per_revolution() / 2).div_euclid(self.counts_per_revolution())
+    }
+
+    pub fn position(&self) -> i64 {
+        self.counts - self.turns() * self.counts_per_revolution()
+    }
+
+    pub fn revolutions(&self) -> f64 {
+        self.counts as f64 / self.counts_per_revolution() as f64
+    }
+
+    pub fn angle_rad(&self) -> f64 {
+        2.0 * PI * self.revolutions()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AbsolutePositionTracker {
+    last: Option<AbsolutePosition>,
+    counter_wraps: i64,
+    turn_tracker: Option<TurnTracker>,
+}
+
+impl AbsolutePositionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn resume(position: AbsolutePosition) -> Self {
+        Self {
+            last: Some(position),
+            ..Self::default()
+        }
+    }
+
+    pub fn counter_wraps(&self) -> i64 {
+        self.counter_wraps
+    }
+
+    pub fn turns(&self) -> i64 {
+        self.last.map_or(0, |position| position.turns())
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn update(
+        &mut self,
+        response: &(impl PositionAndStatus + ?Sized),
+    ) -> Result<AbsolutePosition> {
+        match response.multiturn_count() {
+            Some(_) => Ok(self.update_multiturn(response)),
+            None => {
+                self.turn_tracker(response.resolution())
+                    .update_position(response.position())?;
+                Ok(self.sync_turn_tracker())
+            }
+        }
+    }
+
+    pub fn force_update(
+        &mut self,
+        response: &(impl PositionAndStatus + ?Sized),
+    ) -> AbsolutePosition {
+        match response.multiturn_count() {
+            Some(_) => self.update_multiturn(response),
+            None => {
+                self.turn_tracker(response.resolution())
+                    .force_update_position(response.position());
+                self.sync_turn_tracker()
+            }
+        }
+    }
+
+    fn update_multiturn(
+        &mut self,
+        response: &(impl PositionAndStatus + ?Sized),
+    ) -> AbsolutePosition {
+        let raw = AbsolutePosition::from_response(response);
+        let modulus = raw.counts_per_revolution() * MULTITURN_COUNTER_RANGE;
+
+        let counts = match self.last {
+            Some(previous) => {
+                previous.counts() + wrapping_delta(previous.counts(), raw.counts(), modulus)
+            }
+            None => raw.counts(),
+        };
+        self.counter_wraps = (counts - raw.counts()) / modulus;
+        let position = AbsolutePosition::new(counts, raw.resolution());
+        self.last = Some(position);
+        position
+    }
+
+    fn turn_tracker(&mut self, resolution: Resolution) -> &mut TurnTracker {
+        if self
+            .turn_tracker
+            .as_ref()
+            .is_some_and(|t| t.resolution() != resolution)
+        {
+            self.turn_tracker = None;
+        }
+        let last = self.last;
+        self.turn_tracker.get_or_insert_with(|| match last {
+            Some(last) => {
+                let position = AbsolutePosition::new(last.counts(), resolution);
+                TurnTracker::with_state(resolution, position.turns(), position.position() as i16)
+            }
+            None => TurnTracker::new(resolution),
+        })
+    }
+
+    fn sync_turn_tracker(&mut self) -> AbsolutePosition {
+        let tracker = self
+            .turn_tracker
+            .as_ref()
+            .expect("turn tracker is initialized");
+        let position = AbsolutePosition::new(tracker.total_counts(), tracker.resolution());
+        self.counter_wraps = 0;
+        self.last = Some(position);
+        position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{async_serial::MockPositionAndStatus, error::Error};
+
+    fn raw(multiturn_count: Option<i16>, position: i16) -> MockPositionAndStatus {
+        MockPositionAndStatus {
+            multiturn_count,
+            position,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_absolute_position() {
+        let position = AbsolutePosition::from_response(&raw(Some(-2), 4096));
+        assert_eq!(position.counts(), -2 * 16384 + 4096);
+        assert_eq!(position.turns(), -2);
+        assert_eq!(position.position(), 4096);
+        assert_eq!(position.revolutions(), -1.75);
+
+        let position = AbsolutePosition::new(8192, Resolution::default());
+        assert_eq!((position.turns(), position.position()), (1, -8192));
+    }
+
+    #[test]
+    fn test_multiturn_rollover() {
+        let mut tracker = AbsolutePositionTracker::new();
+
+        let start = tracker.update(&raw(Some(i16::MAX), 8000)).unwrap();
+        let position = tracker.update(&raw(Some(i16::MIN), -8000)).unwrap();
+        assert_eq!(position.counts() - start.counts(), 384);
+        assert_eq!(position.turns(), i16::MAX as i64 + 1);
+        assert_eq!(tracker.counter_wraps(), 1);
+        assert_eq!(tracker.turns(), i16::MAX as i64 + 1);
+
+        let position = tracker.update(&raw(Some(i16::MAX), 0)).unwrap();
+        assert_eq!(position.turns(), i16::MAX as i64);
+        assert_eq!(tracker.counter_wraps(), 0);
+    }
+
+    #[test]
+    fn test_singleturn_wrap() {
+        let mut tracker = AbsolutePositionTracker::new();
+        for position in [0, 6000, -6000, -1000, 5000, -5000] {
+            tracker.update(&raw(None, position)).unwrap();
+        }
+        assert_eq!(tracker.counter_wraps(), 0);
+        assert_eq!(tracker.turns(), 2);
+        assert_eq!(tracker.update(&raw(None, 0)).unwrap().turns(), 2);
+
+        assert!(matches!(
+            tracker.update(&raw(None, -8192)),
+            Err(Error::TurnTrackerAmbiguousJump { .. })
+        ));
+        assert_eq!(tracker.force_update(&raw(None, -8192)).turns(), 2);
+    }
+
+    #[test]
+    fn test_resume() {
+        let last = AbsolutePosition::new(-3 * 65536 * 16384 + 100, Resolution::default());
+        let mut tracker = AbsolutePositionTracker::resume(last);
+
+        let position = tracker.update(&raw(Some(0), 120)).unwrap();
+        assert_eq!(position.counts(), last.counts() + 20);
+        assert_eq!(tracker.counter_wraps(), -3);
+    }
+}
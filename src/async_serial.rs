@@ -1,9 +1,27 @@
 mod command;
+mod continuous_stream;
+mod decoder;
+mod discovery;
+mod encoder;
 mod port;
 mod programming_command;
 mod response;
+#[cfg(any(test, feature = "simulator"))]
+mod simulator;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+mod transport;
 
 pub use command::Command;
+pub use continuous_stream::{ContinuousStream, Sample};
+pub use decoder::{Decoder, ResponseDecoder};
+pub use discovery::{
+    discover, find_by_serial, scan, DiscoveredEncoder, ScanReport, SkippedPort, UsbInfo,
+};
+pub use encoder::{CalibrationProgress, Encoder};
 pub use port::*;
-pub use programming_command::ProgrammingCommand;
+pub use programming_command::{ProgrammingCommand, SUPPORTED_BAUD_RATES};
 pub use response::*;
+#[cfg(any(test, feature = "simulator"))]
+pub use simulator::{ContinuousResponseConfig, SimulatedEncoder, SimulatorHandle};
+pub use transport::*;
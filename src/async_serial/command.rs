@@ -1,4 +1,7 @@
-#[derive(Clone, Copy, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Command {
     PositionRequest = 0x31,
     ShortPositionRequest = 0x33,
@@ -12,4 +15,23 @@ impl Command {
     pub(crate) fn to_byte(self) -> u8 {
         self as u8
     }
+
+    pub(crate) fn response_prefix(self) -> Option<u8> {
+        match self {
+            Self::ShortPositionRequest => None,
+            _ => Some(self.to_byte()),
+        }
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x31 => Some(Self::PositionRequest),
+            0x33 => Some(Self::ShortPositionRequest),
+            0x64 => Some(Self::PositionRequestAndDetailedStatus),
+            0x74 => Some(Self::PositionRequestAndTemperature),
+            0x76 => Some(Self::SerialNumber),
+            0x69 => Some(Self::SelfCalibrationStatusRequest),
+            _ => None,
+        }
+    }
 }
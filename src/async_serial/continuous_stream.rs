@@ -0,0 +1,230 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use serialport::SerialPort;
+
+use super::*;
+use crate::{
+    error::{Error, Result},
+    CounterType,
+};
+
+const CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Clone)]
+pub struct Sample<R> {
+    pub response: R,
+    pub timestamp: Instant,
+}
+
+pub struct ContinuousStream<R, T: Transport = Box<dyn SerialPort>> {
+    counter_type: CounterType,
+    receiver: Receiver<Result<Sample<R>>>,
+    latest: Arc<Mutex<Option<Sample<R>>>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<Port<T>>>,
+}
+
+impl<R, T> ContinuousStream<R, T>
+where
+    R: PositionResponse + Clone + Send + 'static,
+    T: Transport + Send + 'static,
+{
+    pub fn start(mut port: Port<T>, counter_type: CounterType, period_micros: u16) -> Result<Self> {
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)?;
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseSetting {
+            auto_start: false,
+            command: R::request_command(),
+            period_micros,
+        })?;
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStart)?;
+
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let latest = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let latest = latest.clone();
+            let stop = stop.clone();
+            std::thread::spawn(move || read_loop(port, counter_type, sender, latest, stop))
+        };
+
+        Ok(Self {
+            counter_type,
+            receiver,
+            latest,
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl<R, T: Transport> ContinuousStream<R, T> {
+    pub fn counter_type(&self) -> CounterType {
+        self.counter_type
+    }
+
+    pub fn recv(&self) -> Result<Sample<R>> {
+        self.receiver
+            .recv()
+            .unwrap_or(Err(Error::AsyncSerialStreamClosed))
+    }
+
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Result<Sample<R>>> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(sample) => Some(sample),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => Some(Err(Error::AsyncSerialStreamClosed)),
+        }
+    }
+
+    pub fn try_recv(&self) -> Option<Result<Sample<R>>> {
+        match self.receiver.try_recv() {
+            Ok(sample) => Some(sample),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(Error::AsyncSerialStreamClosed)),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Result<Sample<R>>> + '_ {
+        self.receiver.iter()
+    }
+
+    pub fn latest(&self) -> Option<Sample<R>>
+    where
+        R: Clone,
+    {
+        self.latest.lock().unwrap().clone()
+    }
+
+    fn join(&mut self) -> Option<Result<Port<T>>> {
+        self.stop.store(true, Ordering::Relaxed);
+        let mut port = self.thread.take()?.join().ok()?;
+        Some(
+            port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+                .and_then(|_| port.clear_buffers())
+                .map(|_| port),
+        )
+    }
+
+    pub fn stop(mut self) -> Result<Port<T>> {
+        self.join().unwrap_or(Err(Error::AsyncSerialStreamClosed))
+    }
+}
+
+impl<R, T: Transport> Drop for ContinuousStream<R, T> {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+impl<T: Transport> Encoder<T> {
+    pub fn into_continuous_stream<R>(self, period_micros: u16) -> Result<ContinuousStream<R, T>>
+    where
+        R: PositionResponse + Clone + Send + 'static,
+        T: Send + 'static,
+    {
+        let counter_type = self.counter_type();
+        ContinuousStream::start(self.into_port(), counter_type, period_micros)
+    }
+}
+
+fn read_loop<R: PositionResponse + Clone, T: Transport>(
+    mut port: Port<T>,
+    counter_type: CounterType,
+    sender: SyncSender<Result<Sample<R>>>,
+    latest: Arc<Mutex<Option<Sample<R>>>>,
+    stop: Arc<AtomicBool>,
+) -> Port<T> {
+    let prefix = R::request_command().response_prefix();
+    let resolution = port.resolution();
+
+    while !stop.load(Ordering::Relaxed) {
+        let mut response = R::with_resolution(counter_type, resolution);
+        let received = match prefix {
+            Some(prefix) => port.receive_prefixed(prefix, response.as_mut()).map(|_| ()),
+            None => port.receive(&mut response),
+        };
+
+        match received {
+            Ok(()) => {
+                let sample = Sample {
+                    response,
+                    timestamp: Instant::now(),
+                };
+                *latest.lock().unwrap() = Some(sample.clone());
+                let _ = sender.try_send(Ok(sample));
+            }
+            Err(Error::AsyncSerialFailedToReceive(e))
+                if e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                let _ = sender.try_send(Err(Error::AsyncSerialFailedToReceive(e)));
+            }
+            Err(e @ Error::AsyncSerialFrameNotFound { .. }) => {
+                let _ = sender.try_send(Err(e));
+            }
+            Err(e) => {
+                let _ = sender.try_send(Err(e));
+                break;
+            }
+        }
+    }
+
+    port
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn test_stream() {
+        let mut sim = SimulatedEncoder::new(CounterType::MultiTurn);
+        sim.set_angle_profile(|t| 2.0 * PI * t.as_secs_f64());
+        sim.set_temperature(42.0);
+        let (port, sim) = sim.connect();
+        let encoder = Encoder::new(port, CounterType::MultiTurn);
+
+        let stream = encoder
+            .into_continuous_stream::<PositionAndTemperature>(1_000)
+            .unwrap();
+        assert!(sim.lock().is_continuous_response_running());
+
+        let samples: Vec<_> = stream.iter().take(10).map(|s| s.unwrap()).collect();
+        for pair in samples.windows(2) {
+            assert!(pair[0].timestamp <= pair[1].timestamp);
+            assert!(pair[0].response.angle_rad() <= pair[1].response.angle_rad());
+        }
+        assert!(samples.iter().all(|s| s.response.temperature() == 42.0));
+        assert!(stream.latest().is_some());
+
+        let mut port = stream.stop().unwrap();
+        assert!(!sim.lock().is_continuous_response_running());
+
+        let mut serial_number = SerialNumber::new();
+        port.request(&mut serial_number).unwrap();
+        assert_eq!(serial_number.as_str().unwrap(), "SIM001");
+    }
+
+    #[test]
+    fn test_stop_on_drop() {
+        let (port, sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect();
+
+        let stream =
+            ContinuousStream::<ShortPosition, _>::start(port, CounterType::SingleTurn, 500)
+                .unwrap();
+        assert!(stream.recv().is_ok());
+        drop(stream);
+
+        assert!(!sim.lock().is_continuous_response_running());
+    }
+}
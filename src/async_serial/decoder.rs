@@ -0,0 +1,303 @@
+use super::{FrameDecode, Response};
+use crate::{error::Result, CounterType, Resolution};
+
+type EmptyFrame<R> = fn(u8, CounterType, Resolution) -> Option<R>;
+type DecodeFrame<R> = fn(&mut R, &[u8]) -> Result<()>;
+
+pub struct Decoder<R> {
+    counter_type: CounterType,
+    resolution: Resolution,
+    buf: Vec<u8>,
+    start: usize,
+    skipped_bytes: u64,
+    frame_prefix: Option<u8>,
+    empty_frame: EmptyFrame<R>,
+    decode_frame: DecodeFrame<R>,
+}
+
+impl<R: FrameDecode> Decoder<R> {
+    pub fn new(counter_type: CounterType) -> Self {
+        Self::with_resolution(counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_framing(
+            counter_type,
+            resolution,
+            R::frame_prefix(),
+            |_, counter_type, resolution| Some(R::empty_frame(counter_type, resolution)),
+            R::decode,
+        )
+    }
+}
+
+impl<R: AsMut<[u8]>> Decoder<R> {
+    fn with_framing(
+        counter_type: CounterType,
+        resolution: Resolution,
+        frame_prefix: Option<u8>,
+        empty_frame: EmptyFrame<R>,
+        decode_frame: DecodeFrame<R>,
+    ) -> Self {
+        Self {
+            counter_type,
+            resolution,
+            buf: Vec::new(),
+            start: 0,
+            skipped_bytes: 0,
+            frame_prefix,
+            empty_frame,
+            decode_frame,
+        }
+    }
+
+    pub fn counter_type(&self) -> CounterType {
+        self.counter_type
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn skipped_bytes(&self) -> u64 {
+        self.skipped_bytes
+    }
+
+    pub fn buffered_bytes(&self) -> usize {
+        self.buf.len() - self.start
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        if self.start > 0 {
+            self.buf.drain(..self.start);
+            self.start = 0;
+        }
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn decode(&mut self, bytes: &[u8]) -> Vec<R> {
+        self.push(bytes);
+        std::iter::from_fn(|| self.next_frame()).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.start = 0;
+    }
+
+    fn skip(&mut self, count: usize) {
+        self.start += count;
+        self.skipped_bytes += count as u64;
+    }
+
+    pub fn next_frame(&mut self) -> Option<R> {
+        loop {
+            if let Some(prefix) = self.frame_prefix {
+                match self.buf[self.start..].iter().position(|&b| b == prefix) {
+                    Some(start) => self.skip(start),
+                    None => {
+                        self.skip(self.buffered_bytes());
+                        return None;
+                    }
+                }
+            }
+
+            let &first = self.buf.get(self.start)?;
+            let Some(mut response) = (self.empty_frame)(first, self.counter_type, self.resolution)
+            else {
+                self.skip(1);
+                continue;
+            };
+            let size = response.as_mut().len();
+            if self.buffered_bytes() < size {
+                return None;
+            }
+
+            match (self.decode_frame)(&mut response, &self.buf[self.start..][..size]) {
+                Ok(()) => {
+                    self.start += size;
+                    return Some(response);
+                }
+                Err(_) => self.skip(1),
+            }
+        }
+    }
+}
+
+impl<R: AsMut<[u8]>> Iterator for Decoder<R> {
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        self.next_frame()
+    }
+}
+
+pub struct ResponseDecoder {
+    inner: Decoder<Response>,
+}
+
+impl ResponseDecoder {
+    pub fn new(counter_type: CounterType) -> Self {
+        Self::with_resolution(counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self {
+            inner: Decoder::with_framing(
+                counter_type,
+                resolution,
+                None,
+                |prefix, counter_type, resolution| {
+                    Response::empty_for_prefix(prefix, counter_type, resolution).ok()
+                },
+                Response::try_decode,
+            ),
+        }
+    }
+
+    pub fn counter_type(&self) -> CounterType {
+        self.inner.counter_type()
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.inner.resolution()
+    }
+
+    pub fn skipped_bytes(&self) -> u64 {
+        self.inner.skipped_bytes()
+    }
+
+    pub fn buffered_bytes(&self) -> usize {
+        self.inner.buffered_bytes()
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.inner.push(bytes);
+    }
+
+    pub fn decode(&mut self, bytes: &[u8]) -> Vec<Response> {
+        self.inner.decode(bytes)
+    }
+
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    pub fn next_frame(&mut self) -> Option<Response> {
+        self.inner.next_frame()
+    }
+}
+
+impl Iterator for ResponseDecoder {
+    type Item = Response;
+
+    fn next(&mut self) -> Option<Response> {
+        self.next_frame()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::async_serial::{
+        Command, Position, PositionAndStatus, PositionAndTemperature, SerialNumber, ShortPosition,
+    };
+
+    #[test]
+    fn test_prefixed() {
+        let mut decoder = Decoder::<Position>::new(CounterType::SingleTurn);
+
+        assert!(decoder.decode(&[0x00, 0x31, 0x40]).is_empty());
+        assert_eq!(decoder.skipped_bytes(), 1);
+        assert_eq!(decoder.buffered_bytes(), 2);
+
+        let frames = decoder.decode(&[0x03, 0xff, 0x31, 0xc0, 0x03, 0x31]);
+        assert_eq!(
+            frames.iter().map(|f| f.position()).collect::<Vec<_>>(),
+            [4096, -4096]
+        );
+        assert_eq!(decoder.skipped_bytes(), 2);
+        assert_eq!(decoder.buffered_bytes(), 1);
+    }
+
+    #[test]
+    fn test_multiturn_with_resolution() {
+        let mut decoder = Decoder::<PositionAndTemperature>::with_resolution(
+            CounterType::MultiTurn,
+            Resolution::BITS_12,
+        );
+
+        let frames = decoder.decode(&[b't', 0x00, 0x02, 0x40, 0x03, 0x00, 0xfa]);
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].multiturn_count(), Some(2));
+        assert_eq!(frames[0].position(), 1024);
+        assert_eq!(frames[0].temperature(), 25.0);
+    }
+
+    #[test]
+    fn test_unprefixed() {
+        let mut decoder = Decoder::<ShortPosition>::new(CounterType::SingleTurn);
+
+        let frames = decoder.decode(&[0x40, 0x03, 0xc0, 0x03, 0x00]);
+        assert_eq!(
+            frames.iter().map(|f| f.position()).collect::<Vec<_>>(),
+            [4096, -4096]
+        );
+        assert_eq!(decoder.buffered_bytes(), 1);
+    }
+
+    #[test]
+    fn test_resynchronize_on_invalid_frame() {
+        let mut decoder = Decoder::<SerialNumber>::new(CounterType::SingleTurn);
+
+        let frames = decoder.decode(b"v\xffvABC123");
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].as_str().unwrap(), "ABC123");
+        assert_eq!(decoder.skipped_bytes(), 2);
+    }
+
+    #[test]
+    fn test_mixed_responses() {
+        let mut decoder = ResponseDecoder::new(CounterType::SingleTurn);
+
+        let mut frames = decoder.decode(b"1\x40\x03\x001\xc0\x03vSIM");
+        frames.extend(decoder.decode(b"0011\x00\x03"));
+        assert_eq!(
+            frames.iter().map(|f| f.command()).collect::<Vec<_>>(),
+            [
+                Command::PositionRequest,
+                Command::PositionRequest,
+                Command::SerialNumber,
+                Command::PositionRequest
+            ]
+        );
+        assert_eq!(
+            frames[1].position_and_status().map(|p| p.position()),
+            Some(-4096)
+        );
+        assert!(matches!(&frames[2], Response::SerialNumber(s) if s.to_string() == "SIM001"));
+        assert_eq!(decoder.skipped_bytes(), 1);
+        assert_eq!(decoder.buffered_bytes(), 0);
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let pos = Position::from_bytes(CounterType::SingleTurn, &[b'1', 0x40, 0x03]).unwrap();
+        assert_eq!(pos.position(), 4096);
+        assert!(Position::from_bytes(CounterType::MultiTurn, &[b'1', 0x40, 0x03]).is_err());
+    }
+
+    #[test]
+    fn test_long_capture() {
+        let mut decoder = Decoder::<Position>::new(CounterType::SingleTurn);
+
+        let capture = [b'1', 0x40, 0x03].repeat(10_000);
+        assert_eq!(decoder.decode(&capture).len(), 10_000);
+        assert_eq!(decoder.buffered_bytes(), 0);
+
+        decoder.push(&[0x00, b'1']);
+        assert_eq!(decoder.next_frame().map(|f| f.position()), None);
+        assert_eq!(decoder.buffered_bytes(), 1);
+        assert_eq!(decoder.skipped_bytes(), 1);
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::BTreeMap, sync::Mutex, time::Duration};
+
+use serde::Serialize;
+use serialport::{SerialPortInfo, SerialPortType, UsbPortInfo};
+
+use super::{Port, SUPPORTED_BAUD_RATES};
+use crate::error::{Error, Result};
+
+const DISCOVERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+static PORT_CACHE: Mutex<BTreeMap<String, (String, u32)>> = Mutex::new(BTreeMap::new());
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct UsbInfo {
+    pub vid: u16,
+    pub pid: u16,
+    pub serial_number: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+}
+
+impl From<&UsbPortInfo> for UsbInfo {
+    fn from(info: &UsbPortInfo) -> Self {
+        Self {
+            vid: info.vid,
+            pid: info.pid,
+            serial_number: info.serial_number.clone(),
+            manufacturer: info.manufacturer.clone(),
+            product: info.product.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DiscoveredEncoder {
+    pub path: String,
+    pub baud_rate: u32,
+    pub serial_number: String,
+    pub usb: Option<UsbInfo>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SkippedPort {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ScanReport {
+    pub encoders: Vec<DiscoveredEncoder>,
+    pub skipped: Vec<SkippedPort>,
+}
+
+pub fn discover(baud_rates: &[u32]) -> Result<Vec<DiscoveredEncoder>> {
+    scan(baud_rates).map(|report| report.encoders)
+}
+
+pub fn scan(baud_rates: &[u32]) -> Result<ScanReport> {
+    let ports = serialport::available_ports().map_err(Error::AsyncSerialFailedToListPorts)?;
+    let mut report = ScanReport::default();
+    for info in &ports {
+        match probe_port(info, baud_rates) {
+            Ok(encoder) => {
+                remember_port(&encoder);
+                report.encoders.push(encoder);
+            }
+            Err(skipped) => report.skipped.push(skipped),
+        }
+    }
+    Ok(report)
+}
+
+pub fn find_by_serial(serial_number: &str) -> Result<DiscoveredEncoder> {
+    let ports = serialport::available_ports().map_err(Error::AsyncSerialFailedToListPorts)?;
+    ports
+        .iter()
+        .filter_map(|info| probe_port(info, SUPPORTED_BAUD_RATES).ok())
+        .inspect(remember_port)
+        .find(|encoder| encoder.serial_number == serial_number)
+        .ok_or_else(|| Error::AsyncSerialEncoderNotFound(serial_number.to_owned()))
+}
+
+pub(crate) fn cached_port(serial_number: &str) -> Option<(String, u32)> {
+    port_cache().get(serial_number).cloned()
+}
+
+pub(crate) fn forget_port(serial_number: &str) {
+    port_cache().remove(serial_number);
+}
+
+fn remember_port(encoder: &DiscoveredEncoder) {
+    port_cache().insert(
+        encoder.serial_number.clone(),
+        (encoder.path.clone(), encoder.baud_rate),
+    );
+}
+
+fn port_cache() -> std::sync::MutexGuard<'static, BTreeMap<String, (String, u32)>> {
+    PORT_CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn probe_port(
+    info: &SerialPortInfo,
+    baud_rates: &[u32],
+) -> std::result::Result<DiscoveredEncoder, SkippedPort> {
+    let skipped = |reason: String| SkippedPort {
+        path: info.port_name.clone(),
+        reason,
+    };
+    let &first = baud_rates
+        .first()
+        .ok_or_else(|| skipped("no baud rates to probe".to_owned()))?;
+    let mut port = Port::try_new(&info.port_name, first, DISCOVERY_TIMEOUT)
+        .map_err(|e| skipped(e.to_string()))?;
+    let (baud_rate, serial_number) = port
+        .probe_baud_rates(baud_rates)
+        .map_err(|e| skipped(e.to_string()))?;
+    Ok(DiscoveredEncoder {
+        path: info.port_name.clone(),
+        baud_rate,
+        serial_number: serial_number
+            .as_str()
+            .map_err(|e| skipped(e.to_string()))?
+            .to_owned(),
+        usb: usb_info(&info.port_type),
+    })
+}
+
+fn usb_info(port_type: &SerialPortType) -> Option<UsbInfo> {
+    match port_type {
+        SerialPortType::UsbPort(info) => Some(info.into()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize() {
+        let encoder = DiscoveredEncoder {
+            path: "/dev/ttyUSB0".to_owned(),
+            baud_rate: 1_000_000,
+            serial_number: "ABC123".to_owned(),
+            usb: Some(UsbInfo {
+                vid: 0x0403,
+                pid: 0x6001,
+                serial_number: Some("FT1234".to_owned()),
+                manufacturer: Some("FTDI".to_owned()),
+                product: None,
+            }),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&encoder).unwrap(),
+            r#"{"path":"/dev/ttyUSB0","baud_rate":1000000,"serial_number":"ABC123","usb":{"vid":1027,"pid":24577,"serial_number":"FT1234","manufacturer":"FTDI","product":null}}"#
+        );
+        assert_eq!(usb_info(&SerialPortType::PciPort), None);
+    }
+
+    #[test]
+    fn test_skipped_port() {
+        let info = SerialPortInfo {
+            port_name: "/nonexistent/ttyORBIS".to_owned(),
+            port_type: SerialPortType::Unknown,
+        };
+
+        let skipped = probe_port(&info, SUPPORTED_BAUD_RATES).unwrap_err();
+        assert_eq!(skipped.path, "/nonexistent/ttyORBIS");
+        assert!(!skipped.reason.is_empty());
+    }
+
+    #[test]
+    fn test_port_cache() {
+        let encoder = DiscoveredEncoder {
+            path: "/dev/ttyUSB3".to_owned(),
+            baud_rate: 115_200,
+            serial_number: "CACHE1".to_owned(),
+            usb: None,
+        };
+        assert_eq!(cached_port("CACHE1"), None);
+
+        remember_port(&encoder);
+        assert_eq!(
+            cached_port("CACHE1"),
+            Some(("/dev/ttyUSB3".to_owned(), 115_200))
+        );
+
+        forget_port("CACHE1");
+        assert_eq!(cached_port("CACHE1"), None);
+    }
+}
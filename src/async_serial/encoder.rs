@@ -0,0 +1,230 @@
+mod calibration;
+mod snapshot;
+mod verified;
+
+pub use calibration::CalibrationProgress;
+
+use std::{path::Path, time::Duration};
+
+use serialport::SerialPort;
+
+use super::*;
+use crate::{error::Result, CounterType, Resolution};
+
+pub struct Encoder<T: Transport = Box<dyn SerialPort>> {
+    port: Port<T>,
+    counter_type: CounterType,
+}
+
+impl Encoder {
+    pub fn try_new(
+        path: impl AsRef<Path>,
+        baud_rate: u32,
+        timeout: Duration,
+        counter_type: CounterType,
+    ) -> Result<Self> {
+        Ok(Self::new(
+            Port::try_new(path, baud_rate, timeout)?,
+            counter_type,
+        ))
+    }
+
+    pub fn try_open(
+        path: impl AsRef<Path>,
+        baud_rate: BaudRate,
+        timeout: Duration,
+    ) -> Result<Self> {
+        Self::detect(Port::try_open(path, baud_rate, timeout)?)
+    }
+
+    pub fn open_by_serial(serial_number: &str, timeout: Duration) -> Result<Self> {
+        if let Some((path, baud_rate)) = discovery::cached_port(serial_number) {
+            if let Ok(mut encoder) = Self::try_open(path, BaudRate::Fixed(baud_rate), timeout) {
+                if encoder
+                    .read_serial_number()
+                    .is_ok_and(|s| s.as_str().is_ok_and(|s| s == serial_number))
+                {
+                    return Ok(encoder);
+                }
+            }
+            discovery::forget_port(serial_number);
+        }
+
+        let found = find_by_serial(serial_number)?;
+        Self::try_open(found.path, BaudRate::Fixed(found.baud_rate), timeout)
+    }
+}
+
+impl<T: Transport> Encoder<T> {
+    pub fn detect(mut port: Port<T>) -> Result<Self> {
+        let counter_type = port.detect_counter_type()?;
+        Ok(Self::new(port, counter_type))
+    }
+
+    pub fn detect_and_stop(mut port: Port<T>) -> Result<Self> {
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)?;
+        port.clear_buffers()?;
+        Self::detect(port)
+    }
+
+    pub fn new(port: Port<T>, counter_type: CounterType) -> Self {
+        Self::with_resolution(port, counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(
+        mut port: Port<T>,
+        counter_type: CounterType,
+        resolution: Resolution,
+    ) -> Self {
+        port.set_resolution(resolution);
+        Self { port, counter_type }
+    }
+
+    pub fn counter_type(&self) -> CounterType {
+        self.counter_type
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.port.resolution()
+    }
+
+    pub fn port(&self) -> &Port<T> {
+        &self.port
+    }
+
+    pub fn port_mut(&mut self) -> &mut Port<T> {
+        &mut self.port
+    }
+
+    pub fn into_port(self) -> Port<T> {
+        self.port
+    }
+
+    fn request<R: PrefixedResponse + AsMut<[u8]>>(&mut self, mut response: R) -> Result<R> {
+        self.port.request(&mut response)?;
+        Ok(response)
+    }
+
+    pub fn read_position(&mut self) -> Result<Position> {
+        self.request(Position::with_resolution(
+            self.counter_type,
+            self.resolution(),
+        ))
+    }
+
+    pub fn read_short_position(&mut self) -> Result<ShortPosition> {
+        let mut response = ShortPosition::with_resolution(self.counter_type, self.resolution());
+        self.port.clear_buffers()?;
+        self.port.send_command(&Command::ShortPositionRequest)?;
+        self.port.receive(&mut response)?;
+        Ok(response)
+    }
+
+    pub fn read_detailed_status(&mut self) -> Result<PositionAndDetailedStatus> {
+        self.request(PositionAndDetailedStatus::with_resolution(
+            self.counter_type,
+            self.resolution(),
+        ))
+    }
+
+    pub fn read_temperature(&mut self) -> Result<PositionAndTemperature> {
+        self.request(PositionAndTemperature::with_resolution(
+            self.counter_type,
+            self.resolution(),
+        ))
+    }
+
+    pub fn read_serial_number(&mut self) -> Result<SerialNumber> {
+        let serial_number = self.request(SerialNumber::new())?;
+        serial_number.as_str()?;
+        Ok(serial_number)
+    }
+
+    pub fn read_self_calibration_status(&mut self) -> Result<SelfCalibrationStatus> {
+        self.request(SelfCalibrationStatus::new())
+    }
+
+    pub fn send_programming_command(&mut self, command: &ProgrammingCommand) -> Result<()> {
+        self.port.send_programming_command(command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let mut sim = SimulatedEncoder::new(CounterType::MultiTurn);
+        sim.set_angle(-FRAC_PI_2);
+        sim.set_serial_number("ENC042").unwrap();
+        sim.set_temperature(31.5);
+        let (port, _sim) = sim.connect();
+        let mut encoder = Encoder::new(port, CounterType::MultiTurn);
+
+        let pos = encoder.read_position().unwrap();
+        assert_eq!(pos.multiturn_count(), Some(0));
+        assert_eq!(pos.position(), -4096);
+
+        let pos = encoder.read_short_position().unwrap();
+        assert_approx_eq!(pos.angle_rad(), -FRAC_PI_2);
+
+        let status = encoder.read_detailed_status().unwrap();
+        assert!(!status.is_signal_too_high());
+
+        let temperature = encoder.read_temperature().unwrap();
+        assert_approx_eq!(temperature.temperature(), 31.5);
+
+        assert_eq!(
+            encoder.read_serial_number().unwrap().as_str().unwrap(),
+            "ENC042"
+        );
+
+        let calibration = encoder.read_self_calibration_status().unwrap();
+        assert_eq!(calibration.counter(), 0);
+    }
+
+    #[test]
+    fn test_detect() {
+        let (port, _sim) = SimulatedEncoder::new(CounterType::MultiTurn).connect();
+        let mut encoder = Encoder::detect(port).unwrap();
+        assert_eq!(encoder.counter_type(), CounterType::MultiTurn);
+        assert_eq!(encoder.read_position().unwrap().multiturn_count(), Some(0));
+    }
+
+    #[test]
+    fn test_12bit() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_resolution(Resolution::BITS_12);
+        sim.set_angle(-FRAC_PI_2);
+        let (port, sim) = sim.connect();
+        let mut encoder =
+            Encoder::with_resolution(port, CounterType::SingleTurn, Resolution::BITS_12);
+
+        let pos = encoder.read_position().unwrap();
+        assert_eq!(pos.position(), -1024);
+        assert_approx_eq!(pos.angle_rad(), -FRAC_PI_2);
+
+        encoder
+            .send_programming_command(&ProgrammingCommand::PositionOffsetSetting(-1024))
+            .unwrap();
+        assert_eq!(sim.lock().position_offset(), -1024);
+        assert_eq!(encoder.read_position().unwrap().position(), 0);
+    }
+
+    #[test]
+    fn test_resynchronize() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_angle(FRAC_PI_2);
+        let (port, sim) = sim.connect();
+        let mut encoder = Encoder::new(port, CounterType::SingleTurn);
+
+        sim.lock().queue_noise(&[0x00, 0x03, 0x40]);
+        assert_eq!(encoder.read_position().unwrap().position(), 4096);
+        assert_eq!(encoder.port().skipped_bytes(), 3);
+    }
+}
@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    async_serial::{Encoder, ProgrammingCommand, Transport},
+    error::{Error, Result},
+    AbsolutePositionTracker,
+};
+
+const CALIBRATION_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const POSITION_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CalibrationProgress {
+    pub elapsed: Duration,
+    pub revolutions: f64,
+    pub counter: u8,
+}
+
+impl<T: Transport> Encoder<T> {
+    pub fn calibrate(
+        &mut self,
+        timeout: Duration,
+        mut on_progress: impl FnMut(&CalibrationProgress),
+    ) -> Result<()> {
+        let initial_counter = self.read_self_calibration_status()?.counter();
+        self.send_programming_command(&ProgrammingCommand::SelfCalibrationStart)?;
+
+        let start = Instant::now();
+        let mut tracker = AbsolutePositionTracker::new();
+        let mut origin = None;
+        let mut next_status = start + CALIBRATION_POLL_INTERVAL;
+        loop {
+            std::thread::sleep(POSITION_POLL_INTERVAL);
+
+            let position = tracker.update(&self.read_position()?)?;
+            let origin = *origin.get_or_insert(position.revolutions());
+            if Instant::now() < next_status {
+                continue;
+            }
+            next_status += CALIBRATION_POLL_INTERVAL;
+
+            let status = self.read_self_calibration_status()?;
+            on_progress(&CalibrationProgress {
+                elapsed: start.elapsed(),
+                revolutions: position.revolutions() - origin,
+                counter: status.counter(),
+            });
+
+            if status.is_out_of_range() {
+                return Err(Error::AsyncSerialSelfCalibrationOutOfRange);
+            }
+            if status.is_timeout() {
+                return Err(Error::AsyncSerialSelfCalibrationTimeout);
+            }
+            if status.counter() != initial_counter {
+                return self
+                    .send_programming_command(&ProgrammingCommand::ConfigurationParametersSave);
+            }
+            if start.elapsed() >= timeout {
+                return Err(Error::AsyncSerialSelfCalibrationNotFinished(timeout));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::{async_serial::SimulatedEncoder, CounterType};
+
+    #[test]
+    fn test_calibrate() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_angle_profile(|t| 2.0 * PI * t.as_secs_f64() * 4.0);
+        let (mut encoder, sim) = sim.connect_encoder();
+
+        let mut progress = Vec::new();
+        encoder
+            .calibrate(Duration::from_secs(2), |p| progress.push(*p))
+            .unwrap();
+        assert!(!sim.lock().is_self_calibrating());
+        assert_eq!(progress.last().unwrap().counter, 1);
+        assert_eq!(encoder.read_self_calibration_status().unwrap().counter(), 1);
+    }
+
+    #[test]
+    fn test_calibrate_fast_rotation() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_angle_profile(|t| 2.0 * PI * t.as_secs_f64() * 6.0);
+        let (mut encoder, _sim) = sim.connect_encoder();
+
+        let mut progress = Vec::new();
+        encoder
+            .calibrate(Duration::from_secs(2), |p| progress.push(*p))
+            .unwrap();
+        assert!(progress.last().unwrap().revolutions > 0.5);
+    }
+
+    #[test]
+    fn test_calibration_failures() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_self_calibration_out_of_range(true);
+        let (mut encoder, _sim) = sim.connect_encoder();
+        assert!(matches!(
+            encoder.calibrate(Duration::from_secs(1), |_| {}),
+            Err(Error::AsyncSerialSelfCalibrationOutOfRange)
+        ));
+
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_self_calibration_timeout(Duration::from_millis(150));
+        let (mut encoder, _sim) = sim.connect_encoder();
+        assert!(matches!(
+            encoder.calibrate(Duration::from_secs(1), |_| {}),
+            Err(Error::AsyncSerialSelfCalibrationTimeout)
+        ));
+
+        let (mut encoder, _sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect_encoder();
+        assert!(matches!(
+            encoder.calibrate(Duration::from_millis(250), |_| {}),
+            Err(Error::AsyncSerialSelfCalibrationNotFinished(_))
+        ));
+    }
+}
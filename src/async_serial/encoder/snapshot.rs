@@ -0,0 +1,122 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    async_serial::{Encoder, PositionAndStatus, Transport},
+    error::{Error, Result},
+    CalibrationSnapshot, ConfigSnapshot, ObservedContinuousResponse,
+};
+
+impl<T: Transport> Encoder<T> {
+    pub fn snapshot(&mut self, listen: Duration) -> Result<ConfigSnapshot> {
+        let continuous_response = self.listen_for_continuous_response(listen)?;
+
+        let serial_number = self
+            .read_serial_number()
+            .ok()
+            .and_then(|s| s.as_str().ok().map(str::to_owned));
+        let position = self.read_position().ok();
+        let self_calibration =
+            self.read_self_calibration_status()
+                .ok()
+                .map(|status| CalibrationSnapshot {
+                    counter: status.counter(),
+                    out_of_range: status.is_out_of_range(),
+                    timeout: status.is_timeout(),
+                });
+
+        Ok(ConfigSnapshot {
+            serial_number,
+            host_baud_rate: self.port.baud_rate(),
+            counter_type: self.counter_type,
+            resolution_bits: self.resolution().bits(),
+            position: position.as_ref().map(|p| p.position()),
+            multiturn_count: position.as_ref().and_then(|p| p.multiturn_count()),
+            continuous_response,
+            self_calibration,
+        })
+    }
+
+    fn listen_for_continuous_response(
+        &mut self,
+        window: Duration,
+    ) -> Result<Option<ObservedContinuousResponse>> {
+        self.port.clear_buffers()?;
+
+        let start = Instant::now();
+        let mut active = false;
+        let mut command = None;
+        let mut first = None;
+        let mut last = start;
+        let mut frames = 0;
+        while start.elapsed() < window {
+            match self.port.receive_response(self.counter_type) {
+                Ok(response) => {
+                    active = true;
+                    if *command.get_or_insert(response.command()) == response.command() {
+                        last = Instant::now();
+                        first.get_or_insert(last);
+                        frames += 1;
+                    }
+                }
+                Err(Error::AsyncSerialFailedToReceive(e))
+                    if e.kind() == io::ErrorKind::TimedOut => {}
+                Err(_) => active = true,
+            }
+        }
+
+        Ok(active.then(|| ObservedContinuousResponse {
+            command,
+            period_micros: first
+                .filter(|_| frames >= 2)
+                .map(|first| (last.duration_since(first) / (frames - 1)).as_micros() as u32),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        async_serial::{Command, ProgrammingCommand, SimulatedEncoder},
+        CounterType,
+    };
+
+    #[test]
+    fn test_snapshot() {
+        let mut sim = SimulatedEncoder::new(CounterType::MultiTurn);
+        sim.set_serial_number("SNAP01").unwrap();
+        let (mut port, _sim) = sim.connect();
+        port.set_timeout(Duration::from_millis(20)).unwrap();
+        let mut encoder = Encoder::new(port, CounterType::MultiTurn);
+
+        let snapshot = encoder.snapshot(Duration::from_millis(50)).unwrap();
+        assert_eq!(snapshot.serial_number.as_deref(), Some("SNAP01"));
+        assert_eq!(snapshot.multiturn_count, Some(0));
+        assert_eq!(snapshot.continuous_response, None);
+        assert_eq!(snapshot.self_calibration.map(|c| c.counter), Some(0));
+
+        encoder
+            .send_programming_command(&ProgrammingCommand::ContinuousResponseSetting {
+                auto_start: false,
+                command: Command::PositionRequestAndTemperature,
+                period_micros: 5_000,
+            })
+            .unwrap();
+        encoder
+            .send_programming_command(&ProgrammingCommand::ContinuousResponseStart)
+            .unwrap();
+
+        let snapshot = encoder.snapshot(Duration::from_millis(200)).unwrap();
+        let observed = snapshot.continuous_response.unwrap();
+        assert_eq!(
+            observed.command,
+            Some(Command::PositionRequestAndTemperature)
+        );
+        let period = observed.period_micros.unwrap();
+        assert!((4_000..6_500).contains(&period), "period {period}");
+        assert_eq!(snapshot.serial_number.as_deref(), Some("SNAP01"));
+    }
+}
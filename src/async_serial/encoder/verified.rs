@@ -0,0 +1,360 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    async_serial::{Command, Encoder, PositionAndStatus, ProgrammingCommand, Response, Transport},
+    error::{Error, Result},
+    turn_tracker::wrapping_delta,
+};
+
+const POSITION_TOLERANCE: i64 = 16;
+const PERIOD_TOLERANCE: f64 = 0.25;
+const MIN_MEASUREMENT_WINDOW: Duration = Duration::from_millis(100);
+const MIN_MEASURED_FRAMES: u32 = 3;
+
+impl<T: Transport> Encoder<T> {
+    pub fn send_programming_command_verified(
+        &mut self,
+        command: &ProgrammingCommand,
+    ) -> Result<()> {
+        match *command {
+            ProgrammingCommand::PositionOffsetSetting(offset) => self.set_position_offset(offset),
+            ProgrammingCommand::MultiturnCounterSetting(count) => {
+                self.send_programming_command(command)?;
+                let actual = self.read_position()?.multiturn_count();
+                if actual == Some(count) {
+                    Ok(())
+                } else {
+                    Err(Error::AsyncSerialMultiturnCounterNotApplied {
+                        expected: count,
+                        actual,
+                    })
+                }
+            }
+            ProgrammingCommand::BaudRateSetting(baud_rate) => {
+                if !ProgrammingCommand::is_supported_baud_rate(baud_rate) {
+                    return Err(Error::AsyncSerialUnsupportedBaudRate(baud_rate));
+                }
+                self.send_programming_command(command)?;
+                self.port
+                    .set_baud_rate(baud_rate)
+                    .and_then(|_| self.port.clear_buffers())
+                    .and_then(|_| self.read_serial_number())
+                    .map(|_| ())
+                    .map_err(|source| Error::AsyncSerialBaudRateNotApplied {
+                        baud_rate,
+                        source: Box::new(source),
+                    })
+            }
+            ProgrammingCommand::ContinuousResponseSetting {
+                auto_start,
+                command: response,
+                period_micros,
+            } => {
+                self.send_programming_command(command)?;
+                self.verify_continuous_response(response, period_micros, auto_start)
+            }
+            _ => self.send_programming_command(command),
+        }
+    }
+
+    pub fn change_baud_rate(&mut self, baud_rate: u32, save: bool) -> Result<()> {
+        let previous = self.port.baud_rate();
+        if let Err(e) =
+            self.send_programming_command_verified(&ProgrammingCommand::BaudRateSetting(baud_rate))
+        {
+            self.rollback_baud_rate(previous, baud_rate);
+            return Err(e);
+        }
+
+        if save {
+            self.send_programming_command(&ProgrammingCommand::ConfigurationParametersSave)?;
+        }
+        Ok(())
+    }
+
+    fn rollback_baud_rate(&mut self, previous: Option<u32>, baud_rate: u32) {
+        if let Some(previous) = previous {
+            match self.port.probe_baud_rates(&[previous, baud_rate]) {
+                Ok((answering, _)) if answering != previous => {
+                    let _ = self
+                        .send_programming_command(&ProgrammingCommand::BaudRateSetting(previous));
+                    let _ = self.port.set_baud_rate(previous);
+                }
+                Ok(_) => {}
+                Err(_) => {
+                    let _ = self.port.set_baud_rate(previous);
+                }
+            }
+        }
+        let _ = self.port.clear_buffers();
+    }
+
+    fn set_position_offset(&mut self, offset: i16) -> Result<()> {
+        let target = ProgrammingCommand::PositionOffsetSetting(offset);
+        let unverified = |source| Error::AsyncSerialPositionOffsetNotVerified {
+            offset,
+            source: Box::new(source),
+        };
+
+        self.send_programming_command(&ProgrammingCommand::PositionOffsetSetting(0))?;
+        let raw = match self.read_position() {
+            Ok(position) => position.position(),
+            Err(e) => {
+                self.send_programming_command(&target)?;
+                return Err(unverified(e));
+            }
+        };
+        self.send_programming_command(&target)?;
+        let actual = self.read_position().map_err(unverified)?.position();
+
+        let counts = self.resolution().counts_per_revolution() as i64;
+        let expected = wrapping_delta(0, raw as i64 - offset as i64, counts) as i16;
+        if wrapping_delta(expected.into(), actual.into(), counts).abs() <= POSITION_TOLERANCE {
+            Ok(())
+        } else {
+            Err(Error::AsyncSerialPositionOffsetNotApplied { expected, actual })
+        }
+    }
+
+    fn verify_continuous_response(
+        &mut self,
+        command: Command,
+        period_micros: u16,
+        auto_start: bool,
+    ) -> Result<()> {
+        let expected = Duration::from_micros(period_micros.into());
+
+        self.send_programming_command(&ProgrammingCommand::ContinuousResponseStart)?;
+        let measured = self.measure_period(command, expected);
+        if !auto_start {
+            self.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)?;
+            self.port.clear_buffers()?;
+        }
+
+        match measured? {
+            Some(actual)
+                if (actual.as_secs_f64() - expected.as_secs_f64()).abs()
+                    <= expected.as_secs_f64() * PERIOD_TOLERANCE =>
+            {
+                Ok(())
+            }
+            actual => Err(Error::AsyncSerialContinuousResponseNotApplied { expected, actual }),
+        }
+    }
+
+    fn measure_period(&mut self, command: Command, expected: Duration) -> Result<Option<Duration>> {
+        let window = (expected * 10).max(MIN_MEASUREMENT_WINDOW);
+        let mut response =
+            Response::empty_for_command(command, self.counter_type, self.resolution());
+
+        let mut first = None;
+        let mut last = Instant::now();
+        let mut frames = 0;
+        loop {
+            let received = match command.response_prefix() {
+                Some(prefix) => self
+                    .port
+                    .receive_prefixed(prefix, response.as_mut())
+                    .map(|_| ()),
+                None => self.port.receive(&mut response),
+            };
+            match received {
+                Ok(()) => {}
+                Err(Error::AsyncSerialFailedToReceive(e))
+                    if e.kind() == io::ErrorKind::TimedOut =>
+                {
+                    break
+                }
+                Err(Error::AsyncSerialFrameNotFound { .. }) => break,
+                Err(e) => return Err(e),
+            }
+
+            last = Instant::now();
+            frames += 1;
+            if last.duration_since(*first.get_or_insert(last)) >= window {
+                break;
+            }
+        }
+
+        Ok(match first {
+            Some(first) if frames >= MIN_MEASURED_FRAMES => {
+                Some(last.duration_since(first) / (frames - 1))
+            }
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use super::*;
+    use crate::{
+        async_serial::{port::MAX_SKIPPED_BYTES, SimulatedEncoder},
+        CounterType,
+    };
+
+    #[test]
+    fn test_position_offset() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_angle(FRAC_PI_2);
+        let (mut encoder, sim) = sim.connect_encoder();
+
+        encoder
+            .send_programming_command_verified(&ProgrammingCommand::PositionOffsetSetting(-6000))
+            .unwrap();
+        assert_eq!(sim.lock().position_offset(), -6000);
+        assert_eq!(
+            encoder.read_position().unwrap().position(),
+            4096 + 6000 - 16384
+        );
+
+        sim.lock().queue_noise(&[0; MAX_SKIPPED_BYTES]);
+        assert!(matches!(
+            encoder
+                .send_programming_command_verified(&ProgrammingCommand::PositionOffsetSetting(500)),
+            Err(Error::AsyncSerialPositionOffsetNotVerified { offset: 500, source })
+                if matches!(*source, Error::AsyncSerialFrameNotFound { .. })
+        ));
+        assert_eq!(sim.lock().position_offset(), 500);
+
+        encoder
+            .send_programming_command_verified(&ProgrammingCommand::PositionOffsetSetting(-6000))
+            .unwrap();
+        sim.lock().set_write_protected(true);
+        assert!(matches!(
+            encoder.send_programming_command_verified(&ProgrammingCommand::PositionOffsetSetting(
+                1000
+            )),
+            Err(Error::AsyncSerialPositionOffsetNotApplied {
+                expected: -7288,
+                actual: -6288
+            })
+        ));
+    }
+
+    #[test]
+    fn test_multiturn_counter() {
+        let (mut encoder, sim) = SimulatedEncoder::new(CounterType::MultiTurn).connect_encoder();
+
+        encoder
+            .send_programming_command_verified(&ProgrammingCommand::MultiturnCounterSetting(-42))
+            .unwrap();
+        assert_eq!(
+            encoder.read_position().unwrap().multiturn_count(),
+            Some(-42)
+        );
+
+        sim.lock().set_write_protected(true);
+        assert!(matches!(
+            encoder
+                .send_programming_command_verified(&ProgrammingCommand::MultiturnCounterSetting(7)),
+            Err(Error::AsyncSerialMultiturnCounterNotApplied {
+                expected: 7,
+                actual: Some(-42)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_baud_rate() {
+        let (mut encoder, sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect_encoder();
+
+        encoder
+            .send_programming_command_verified(&ProgrammingCommand::BaudRateSetting(230_400))
+            .unwrap();
+        assert_eq!(sim.lock().baud_rate(), 230_400);
+        assert_eq!(encoder.port().transport().baud_rate(), Some(230_400));
+
+        sim.lock().set_write_protected(true);
+        assert!(matches!(
+            encoder
+                .send_programming_command_verified(&ProgrammingCommand::BaudRateSetting(460_800)),
+            Err(Error::AsyncSerialBaudRateNotApplied {
+                baud_rate: 460_800,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_change_baud_rate() {
+        let (mut encoder, sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect_encoder();
+        encoder.port_mut().set_baud_rate(115_200).unwrap();
+
+        assert!(matches!(
+            encoder.change_baud_rate(250_000, true),
+            Err(Error::AsyncSerialUnsupportedBaudRate(250_000))
+        ));
+        assert_eq!(encoder.port().baud_rate(), Some(115_200));
+
+        encoder.change_baud_rate(921_600, true).unwrap();
+        assert_eq!(encoder.port().baud_rate(), Some(921_600));
+        sim.lock().power_cycle();
+        assert_eq!(sim.lock().baud_rate(), 921_600);
+        assert!(encoder.read_serial_number().is_ok());
+
+        sim.lock().set_write_protected(true);
+        assert!(matches!(
+            encoder.change_baud_rate(57_600, false),
+            Err(Error::AsyncSerialBaudRateNotApplied {
+                baud_rate: 57_600,
+                ..
+            })
+        ));
+        assert_eq!(encoder.port().baud_rate(), Some(921_600));
+        assert!(encoder.read_serial_number().is_ok());
+    }
+
+    #[test]
+    fn test_change_baud_rate_without_echo() {
+        let (mut encoder, sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect_encoder();
+        encoder.port_mut().set_baud_rate(115_200).unwrap();
+
+        sim.lock().set_drop_final_echo(true);
+        assert!(matches!(
+            encoder.change_baud_rate(460_800, false),
+            Err(Error::AsyncSerialFailedToReceive(_))
+        ));
+        sim.lock().set_drop_final_echo(false);
+
+        assert_eq!(sim.lock().baud_rate(), 115_200);
+        assert_eq!(encoder.port().baud_rate(), Some(115_200));
+        assert!(encoder.read_serial_number().is_ok());
+    }
+
+    #[test]
+    fn test_continuous_response() {
+        let (mut encoder, sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect_encoder();
+
+        let setting = ProgrammingCommand::ContinuousResponseSetting {
+            auto_start: false,
+            command: Command::PositionRequest,
+            period_micros: 5_000,
+        };
+        encoder.send_programming_command_verified(&setting).unwrap();
+        assert!(!sim.lock().is_continuous_response_running());
+        assert!(encoder.read_position().is_ok());
+
+        let auto_start = ProgrammingCommand::ContinuousResponseSetting {
+            auto_start: true,
+            command: Command::PositionRequest,
+            period_micros: 5_000,
+        };
+        encoder
+            .send_programming_command_verified(&auto_start)
+            .unwrap();
+        assert!(sim.lock().is_continuous_response_running());
+
+        let (mut encoder, sim) = SimulatedEncoder::new(CounterType::SingleTurn).connect_encoder();
+        sim.lock().set_write_protected(true);
+        assert!(matches!(
+            encoder.send_programming_command_verified(&setting),
+            Err(Error::AsyncSerialContinuousResponseNotApplied { actual: None, .. })
+        ));
+    }
+}
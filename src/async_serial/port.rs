@@ -1,15 +1,155 @@
-use std::{path::Path, time::Duration};
+use std::{
+    io,
+    net::{TcpStream, ToSocketAddrs},
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 use serialport::{DataBits, Parity, SerialPort, StopBits};
 
-use super::{Command, ProgrammingCommand};
-use crate::error::{Error, Result};
+use super::{
+    Command, Position, PrefixedResponse, ProgrammingCommand, Response, ResponseDecoder,
+    SerialNumber, Transport, SUPPORTED_BAUD_RATES,
+};
+use crate::{
+    error::{Error, Result},
+    CounterType, Resolution,
+};
 
-const PROGRAMMING_UNLOCKING_SEQUENCE: &[u8] = &[0xCD, 0xEF, 0x89, 0xAB];
-const PROGRAMMING_DELAY_BETWEEN_BYTES: Duration = Duration::from_millis(1);
+pub(crate) const PROGRAMMING_DELAY_BETWEEN_BYTES: Duration = Duration::from_millis(1);
+pub(crate) const MAX_SKIPPED_BYTES: usize = 256;
+const BAUD_RATE_PROBE_TIMEOUT: Duration = Duration::from_millis(50);
+pub(crate) const INTER_BYTE_TIMEOUT: Duration = Duration::from_millis(20);
+pub(crate) const COUNTER_TYPE_LISTEN_WINDOW: Duration = Duration::from_millis(100);
 
-pub struct Port {
-    inner: Box<dyn SerialPort>,
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BaudRate {
+    Fixed(u32),
+    Auto,
+}
+
+impl FromStr for BaudRate {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            s => s.parse().map(Self::Fixed),
+        }
+    }
+}
+
+pub(crate) struct PrefixScanner {
+    prefix: u8,
+    skipped: usize,
+}
+
+impl PrefixScanner {
+    pub(crate) fn new(prefix: u8) -> Self {
+        Self { prefix, skipped: 0 }
+    }
+
+    pub(crate) fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    pub(crate) fn push(&mut self, byte: io::Result<u8>) -> Option<Result<()>> {
+        match byte {
+            Err(e) => Some(Err(Error::AsyncSerialFailedToReceive(e))),
+            Ok(byte) if byte == self.prefix => Some(Ok(())),
+            Ok(_) => {
+                self.skipped += 1;
+                (self.skipped >= MAX_SKIPPED_BYTES).then_some(Err(
+                    Error::AsyncSerialFrameNotFound {
+                        prefix: self.prefix,
+                        skipped: self.skipped,
+                    },
+                ))
+            }
+        }
+    }
+}
+
+pub(crate) struct ResponseScanner {
+    counter_type: CounterType,
+    resolution: Resolution,
+    skipped: usize,
+}
+
+impl ResponseScanner {
+    pub(crate) fn new(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self {
+            counter_type,
+            resolution,
+            skipped: 0,
+        }
+    }
+
+    pub(crate) fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    pub(crate) fn push(&mut self, byte: io::Result<u8>) -> Option<Result<Response>> {
+        let byte = match byte {
+            Ok(byte) => byte,
+            Err(e) => return Some(Err(Error::AsyncSerialFailedToReceive(e))),
+        };
+        match Response::empty_for_prefix(byte, self.counter_type, self.resolution) {
+            Ok(mut response) => {
+                response.as_mut()[0] = byte;
+                Some(Ok(response))
+            }
+            Err(e) if self.skipped + 1 >= MAX_SKIPPED_BYTES => Some(Err(e)),
+            Err(_) => {
+                self.skipped += 1;
+                None
+            }
+        }
+    }
+}
+
+pub(crate) fn position_frame_lengths() -> (usize, usize) {
+    (
+        Position::new(CounterType::SingleTurn).as_mut().len(),
+        Position::new(CounterType::MultiTurn).as_mut().len(),
+    )
+}
+
+pub(crate) fn counter_type_from_length(length: usize) -> Result<CounterType> {
+    let (single_turn, multi_turn) = position_frame_lengths();
+    match length {
+        l if l == single_turn => Ok(CounterType::SingleTurn),
+        l if l == multi_turn => Ok(CounterType::MultiTurn),
+        length => Err(Error::AsyncSerialCounterTypeNotDetected { length }),
+    }
+}
+
+pub(crate) fn streaming_counter_type(
+    captured: &[u8],
+    resolution: Resolution,
+) -> Option<CounterType> {
+    let skipped = |counter_type| {
+        let mut decoder = ResponseDecoder::with_resolution(counter_type, resolution);
+        let frames = decoder.decode(captured).len();
+        (frames >= 2).then(|| decoder.skipped_bytes())
+    };
+    match (
+        skipped(CounterType::SingleTurn),
+        skipped(CounterType::MultiTurn),
+    ) {
+        (Some(single), Some(multi)) if single < multi => Some(CounterType::SingleTurn),
+        (Some(single), Some(multi)) if multi < single => Some(CounterType::MultiTurn),
+        (Some(_), None) => Some(CounterType::SingleTurn),
+        (None, Some(_)) => Some(CounterType::MultiTurn),
+        _ => None,
+    }
+}
+
+pub struct Port<T: Transport = Box<dyn SerialPort>> {
+    inner: T,
+    skipped_bytes: u64,
+    resolution: Resolution,
 }
 
 impl Port {
@@ -25,7 +165,186 @@ impl Port {
                 path: path.as_ref().into(),
             })?;
 
-        Ok(Self { inner })
+        Ok(Self::new(inner))
+    }
+
+    pub fn try_open(
+        path: impl AsRef<Path>,
+        baud_rate: BaudRate,
+        timeout: Duration,
+    ) -> Result<Self> {
+        match baud_rate {
+            BaudRate::Fixed(baud_rate) => Self::try_new(path, baud_rate, timeout),
+            BaudRate::Auto => {
+                let mut port = Self::try_new(path, SUPPORTED_BAUD_RATES[0], timeout)?;
+                port.detect_baud_rate()?;
+                Ok(port)
+            }
+        }
+    }
+}
+
+impl Port<TcpStream> {
+    pub fn try_connect(addr: impl ToSocketAddrs, timeout: Duration) -> Result<Self> {
+        let inner = TcpStream::connect(addr).map_err(Error::AsyncSerialFailedToConnect)?;
+        inner
+            .set_nodelay(true)
+            .map_err(Error::AsyncSerialFailedToConnect)?;
+
+        let mut port = Self::new(inner);
+        port.set_timeout(timeout)?;
+        Ok(port)
+    }
+}
+
+impl<T: Transport> Port<T> {
+    pub fn new(transport: T) -> Self {
+        Self {
+            inner: transport,
+            skipped_bytes: 0,
+            resolution: Resolution::default(),
+        }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_transport(self) -> T {
+        self.inner
+    }
+
+    pub fn skipped_bytes(&self) -> u64 {
+        self.skipped_bytes
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.inner.timeout()
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.inner
+            .set_timeout(timeout)
+            .map_err(Error::AsyncSerialFailedToConfigure)
+    }
+
+    pub fn baud_rate(&self) -> Option<u32> {
+        self.inner.baud_rate()
+    }
+
+    pub fn set_baud_rate(&mut self, baud_rate: u32) -> Result<()> {
+        self.inner
+            .set_baud_rate(baud_rate)
+            .map_err(Error::AsyncSerialFailedToConfigure)
+    }
+
+    pub fn detect_baud_rate(&mut self) -> Result<u32> {
+        self.probe_baud_rates(SUPPORTED_BAUD_RATES)
+            .map(|(baud_rate, _)| baud_rate)
+    }
+
+    pub fn probe_baud_rates(&mut self, baud_rates: &[u32]) -> Result<(u32, SerialNumber)> {
+        let current = self.baud_rate();
+        let candidates: Vec<_> = current
+            .filter(|b| baud_rates.contains(b))
+            .into_iter()
+            .chain(baud_rates.iter().copied().filter(|&b| Some(b) != current))
+            .collect();
+
+        self.with_probe_timeout(BAUD_RATE_PROBE_TIMEOUT, |port| {
+            for baud_rate in candidates {
+                port.set_baud_rate(baud_rate)?;
+                if let Some(serial_number) = port.probe_serial_number() {
+                    return Ok((baud_rate, serial_number));
+                }
+            }
+            Err(Error::AsyncSerialBaudRateNotDetected)
+        })
+    }
+
+    pub fn detect_counter_type(&mut self) -> Result<CounterType> {
+        if let Some(counter_type) =
+            self.detect_streaming_counter_type(COUNTER_TYPE_LISTEN_WINDOW)?
+        {
+            return Ok(counter_type);
+        }
+
+        let (single_turn, multi_turn) = position_frame_lengths();
+        let mut frame = vec![0; single_turn.max(multi_turn) + 1];
+
+        self.clear_buffers()?;
+        self.send_command(&Command::PositionRequest)?;
+        self.receive_prefixed(Command::PositionRequest.to_byte(), &mut frame[..1])?;
+        let length = self.with_probe_timeout(INTER_BYTE_TIMEOUT, |port| {
+            let mut length = 1;
+            while length < frame.len() && port.inner.read_exact(&mut frame[length..][..1]).is_ok() {
+                length += 1;
+            }
+            Ok(length)
+        })?;
+
+        counter_type_from_length(length)
+    }
+
+    pub fn detect_streaming_counter_type(
+        &mut self,
+        window: Duration,
+    ) -> Result<Option<CounterType>> {
+        self.clear_buffers()?;
+        let captured = self.with_probe_timeout(INTER_BYTE_TIMEOUT, |port| {
+            let start = Instant::now();
+            let mut captured = Vec::new();
+            let mut buf = [0; 1];
+            while start.elapsed() < window {
+                match port.inner.read_exact(&mut buf) {
+                    Ok(()) => captured.push(buf[0]),
+                    Err(e) if e.kind() == io::ErrorKind::TimedOut => {}
+                    Err(e) => return Err(Error::AsyncSerialFailedToReceive(e)),
+                }
+            }
+            Ok(captured)
+        })?;
+
+        Ok(streaming_counter_type(&captured, self.resolution))
+    }
+
+    fn with_probe_timeout<R>(
+        &mut self,
+        timeout: Duration,
+        probe: impl FnOnce(&mut Self) -> Result<R>,
+    ) -> Result<R> {
+        let previous = self.timeout();
+        self.set_timeout(timeout)?;
+        let result = probe(self);
+        self.set_timeout(previous)?;
+        result
+    }
+
+    fn probe_serial_number(&mut self) -> Option<SerialNumber> {
+        let mut serial_number = SerialNumber::new();
+        self.request(&mut serial_number).ok()?;
+        serial_number
+            .as_str()
+            .is_ok_and(|s| s.chars().all(|c| c.is_ascii_graphic()))
+            .then_some(serial_number)
+    }
+
+    pub fn clear_buffers(&mut self) -> Result<()> {
+        self.inner
+            .clear_buffers()
+            .map_err(Error::AsyncSerialFailedToConfigure)
     }
 
     fn send_byte(&mut self, byte: u8) -> Result<()> {
@@ -53,14 +372,7 @@ impl Port {
     }
 
     pub fn send_programming_command(&mut self, command: &ProgrammingCommand) -> Result<()> {
-        let mut bytes = Vec::new();
-        bytes.extend(PROGRAMMING_UNLOCKING_SEQUENCE);
-        bytes.push(command.to_byte());
-        if let Some(additional_data) = command.additional_data() {
-            bytes.extend(additional_data);
-        }
-
-        for byte in bytes {
+        for byte in command.to_bytes(self.resolution) {
             self.send_byte(byte)?;
             std::thread::sleep(PROGRAMMING_DELAY_BETWEEN_BYTES);
 
@@ -76,4 +388,273 @@ impl Port {
             .map_err(Error::AsyncSerialFailedToReceive)?;
         Ok(())
     }
+
+    pub fn receive_frame<R: PrefixedResponse + AsMut<[u8]>>(
+        &mut self,
+        response: &mut R,
+    ) -> Result<usize> {
+        self.receive_prefixed(R::command().to_byte(), response.as_mut())
+    }
+
+    pub(crate) fn receive_prefixed(&mut self, prefix: u8, buf: &mut [u8]) -> Result<usize> {
+        let mut scanner = PrefixScanner::new(prefix);
+        let synced = loop {
+            let byte = self.inner.read_exact(&mut buf[..1]).map(|()| buf[0]);
+            if let Some(synced) = scanner.push(byte) {
+                break synced;
+            }
+        };
+        self.skipped_bytes += scanner.skipped() as u64;
+        synced?;
+
+        self.inner
+            .read_exact(&mut buf[1..])
+            .map_err(Error::AsyncSerialFailedToReceive)?;
+        Ok(scanner.skipped())
+    }
+
+    pub fn receive_response(&mut self, counter_type: CounterType) -> Result<Response> {
+        let mut scanner = ResponseScanner::new(counter_type, self.resolution);
+        let mut prefix = [0; 1];
+        let response = loop {
+            let byte = self.inner.read_exact(&mut prefix).map(|()| prefix[0]);
+            if let Some(response) = scanner.push(byte) {
+                break response;
+            }
+        };
+        self.skipped_bytes += scanner.skipped() as u64;
+        let mut response = response?;
+
+        self.inner
+            .read_exact(&mut response.as_mut()[1..])
+            .map_err(Error::AsyncSerialFailedToReceive)?;
+        if let Response::SerialNumber(serial_number) = &response {
+            serial_number.as_str()?;
+        }
+        Ok(response)
+    }
+
+    pub fn request<R: PrefixedResponse + AsMut<[u8]>>(
+        &mut self,
+        response: &mut R,
+    ) -> Result<usize> {
+        self.clear_buffers()?;
+        self.send_command(&R::command())?;
+        self.receive_frame(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::async_serial::{Encoder, MemoryPipe, SimulatedEncoder};
+
+    #[test]
+    fn test_send_command() {
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+
+        port.send_command(&Command::SerialNumber).unwrap();
+
+        let mut buf = [0; 1];
+        device.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [b'v']);
+    }
+
+    #[test]
+    fn test_send_programming_command() {
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+
+        let echo = std::thread::spawn(move || {
+            let mut received = Vec::new();
+            let mut buf = [0; 1];
+            for _ in 0..5 {
+                device.read_exact(&mut buf).unwrap();
+                device.write_all(&[0x00, buf[0]]).unwrap();
+                received.push(buf[0]);
+            }
+            received
+        });
+
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+            .unwrap();
+
+        assert_eq!(echo.join().unwrap(), [0xCD, 0xEF, 0x89, 0xAB, b'P']);
+    }
+
+    #[test]
+    fn test_receive_frame() {
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+
+        device.write_all(b"\x00\xFF12v123456v654321").unwrap();
+
+        let mut serial_number = SerialNumber::new();
+        assert_eq!(port.receive_frame(&mut serial_number).unwrap(), 4);
+        assert_eq!(serial_number.as_str().unwrap(), "123456");
+        assert_eq!(port.receive_frame(&mut serial_number).unwrap(), 0);
+        assert_eq!(serial_number.as_str().unwrap(), "654321");
+        assert_eq!(port.skipped_bytes(), 4);
+    }
+
+    #[test]
+    fn test_receive_frame_not_found() {
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+
+        device.write_all(&[0; MAX_SKIPPED_BYTES]).unwrap();
+
+        let mut serial_number = SerialNumber::new();
+        assert!(matches!(
+            port.receive_frame(&mut serial_number),
+            Err(Error::AsyncSerialFrameNotFound {
+                prefix: b'v',
+                skipped: MAX_SKIPPED_BYTES
+            })
+        ));
+    }
+
+    #[test]
+    fn test_receive_response() {
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+
+        device.write_all(b"\x00\xFFv1234561\x40\x03").unwrap();
+
+        let response = port.receive_response(CounterType::SingleTurn).unwrap();
+        assert!(matches!(response, Response::SerialNumber(s) if s.to_string() == "123456"));
+        let response = port.receive_response(CounterType::SingleTurn).unwrap();
+        assert_eq!(response.command(), Command::PositionRequest);
+        assert_eq!(
+            response.position_and_status().map(|p| p.position()),
+            Some(4096)
+        );
+        assert_eq!(port.skipped_bytes(), 2);
+    }
+
+    #[test]
+    fn test_detect_baud_rate() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_baud_rate(921_600);
+        let (mut port, sim) = sim.connect();
+        port.set_baud_rate(115_200).unwrap();
+        port.set_timeout(Duration::from_millis(200)).unwrap();
+
+        assert_eq!(port.detect_baud_rate().unwrap(), 921_600);
+        assert_eq!(port.baud_rate(), Some(921_600));
+        assert_eq!(port.timeout(), Duration::from_millis(200));
+
+        sim.lock().set_serial_number("AB\tCD1").unwrap();
+        assert!(matches!(
+            port.detect_baud_rate(),
+            Err(Error::AsyncSerialBaudRateNotDetected)
+        ));
+    }
+
+    #[test]
+    fn test_detect_counter_type() {
+        for counter_type in [CounterType::SingleTurn, CounterType::MultiTurn] {
+            let (mut port, _sim) = SimulatedEncoder::new(counter_type).connect();
+            port.set_timeout(Duration::from_millis(200)).unwrap();
+
+            assert_eq!(port.detect_counter_type().unwrap(), counter_type);
+            assert_eq!(port.timeout(), Duration::from_millis(200));
+        }
+
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+        let timeout = port.timeout();
+        let device = std::thread::spawn(move || {
+            let mut byte = [0; 1];
+            device.read_exact(&mut byte).unwrap();
+            device
+                .write_all(&[b'1', 0x00, 0x00, 0x40, 0x00, 0x00])
+                .unwrap();
+            device
+        });
+        assert!(matches!(
+            port.detect_counter_type(),
+            Err(Error::AsyncSerialCounterTypeNotDetected { length: 6 })
+        ));
+        assert_eq!(port.timeout(), timeout);
+        device.join().unwrap();
+    }
+
+    #[test]
+    fn test_detect_counter_type_while_streaming() {
+        for counter_type in [CounterType::SingleTurn, CounterType::MultiTurn] {
+            let mut sim = SimulatedEncoder::new(counter_type);
+            for byte in (ProgrammingCommand::ContinuousResponseSetting {
+                auto_start: true,
+                command: Command::PositionRequest,
+                period_micros: 5_000,
+            })
+            .to_bytes(Resolution::default())
+            .into_iter()
+            .chain(ProgrammingCommand::ConfigurationParametersSave.to_bytes(Resolution::default()))
+            {
+                sim.handle_byte(byte);
+            }
+            sim.power_cycle();
+            let (mut port, sim) = sim.connect();
+            port.set_timeout(Duration::from_millis(200)).unwrap();
+
+            assert_eq!(
+                port.detect_streaming_counter_type(Duration::from_millis(100))
+                    .unwrap(),
+                Some(counter_type)
+            );
+            assert_eq!(port.detect_counter_type().unwrap(), counter_type);
+            assert!(sim.lock().is_continuous_response_running());
+
+            let mut encoder = Encoder::detect_and_stop(port).unwrap();
+            assert_eq!(encoder.counter_type(), counter_type);
+            assert!(!sim.lock().is_continuous_response_running());
+            assert_eq!(
+                encoder
+                    .port_mut()
+                    .detect_streaming_counter_type(Duration::from_millis(50))
+                    .unwrap(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_probe_restores_timeout() {
+        let (mut port, _sim) = SimulatedEncoder::new(CounterType::MultiTurn).connect();
+        let timeout = port.timeout();
+
+        port.detect_counter_type().unwrap();
+        assert_eq!(port.timeout(), timeout);
+        port.detect_baud_rate().unwrap();
+        assert_eq!(port.timeout(), timeout);
+    }
+
+    #[test]
+    fn test_parse_baud_rate() {
+        assert_eq!("auto".parse(), Ok(BaudRate::Auto));
+        assert_eq!("115200".parse(), Ok(BaudRate::Fixed(115_200)));
+        assert!("fast".parse::<BaudRate>().is_err());
+    }
+
+    #[test]
+    fn test_request_flushes_stale_input() {
+        let (host, mut device) = MemoryPipe::pair();
+        let mut port = Port::new(host);
+
+        device.write_all(b"v000000").unwrap();
+        let responder = std::thread::spawn(move || {
+            let mut buf = [0; 1];
+            device.read_exact(&mut buf).unwrap();
+            device.write_all(b"v123456").unwrap();
+            device
+        });
+
+        let mut serial_number = SerialNumber::new();
+        assert_eq!(port.request(&mut serial_number).unwrap(), 0);
+        assert_eq!(serial_number.as_str().unwrap(), "123456");
+        responder.join().unwrap();
+    }
 }
@@ -1,7 +1,12 @@
 use super::Command;
-use crate::COUNTS_PER_REVOLUTION;
+use crate::Resolution;
 
-#[derive(Clone, Copy, Debug)]
+pub(crate) const PROGRAMMING_UNLOCKING_SEQUENCE: &[u8] = &[0xCD, 0xEF, 0x89, 0xAB];
+pub const SUPPORTED_BAUD_RATES: &[u32] = &[
+    9_600, 19_200, 38_400, 57_600, 115_200, 230_400, 460_800, 921_600, 1_000_000, 2_000_000,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ProgrammingCommand {
     PositionOffsetSetting(i16),
     MultiturnCounterSetting(i16),
@@ -15,9 +20,14 @@ pub enum ProgrammingCommand {
     ContinuousResponseStop,
     ConfigurationParametersSave,
     ConfigurationParametersReset,
+    SelfCalibrationStart,
 }
 
 impl ProgrammingCommand {
+    pub fn is_supported_baud_rate(baud_rate: u32) -> bool {
+        SUPPORTED_BAUD_RATES.contains(&baud_rate)
+    }
+
     pub(crate) fn to_byte(self) -> u8 {
         match self {
             Self::PositionOffsetSetting(_) => b'Z',
@@ -32,16 +42,17 @@ impl ProgrammingCommand {
             Self::ContinuousResponseStop => b'P',
             Self::ConfigurationParametersSave => b'c',
             Self::ConfigurationParametersReset => b'r',
+            Self::SelfCalibrationStart => b'A',
         }
     }
 
-    pub(crate) fn additional_data(&self) -> Option<Vec<u8>> {
+    pub(crate) fn additional_data(&self, resolution: Resolution) -> Option<Vec<u8>> {
         match self {
             Self::PositionOffsetSetting(offset) => {
                 let offset = if *offset >= 0 {
                     *offset as u32
                 } else {
-                    (*offset + COUNTS_PER_REVOLUTION as i16) as u32
+                    (*offset as i32 + resolution.counts_per_revolution() as i32) as u32
                 };
                 let mut data = Vec::new();
                 data.extend(u32::to_be_bytes(offset));
@@ -71,4 +82,110 @@ impl ProgrammingCommand {
             _ => None,
         }
     }
+
+    pub(crate) fn to_bytes(self, resolution: Resolution) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend(PROGRAMMING_UNLOCKING_SEQUENCE);
+        bytes.push(self.to_byte());
+        if let Some(additional_data) = self.additional_data(resolution) {
+            bytes.extend(additional_data);
+        }
+        bytes
+    }
+
+    #[cfg(any(test, feature = "simulator"))]
+    pub(crate) fn additional_data_size(byte: u8) -> Option<usize> {
+        match byte {
+            b'Z' | b'M' | b'B' | b'T' => Some(4),
+            b'S' | b'P' | b'c' | b'r' | b'A' => Some(0),
+            _ => None,
+        }
+    }
+
+    #[cfg(any(test, feature = "simulator"))]
+    pub(crate) fn from_bytes(byte: u8, data: &[u8], resolution: Resolution) -> Option<Self> {
+        if Self::additional_data_size(byte)? != data.len() {
+            return None;
+        }
+        match byte {
+            b'Z' => {
+                let offset = u32::from_be_bytes(data.try_into().ok()?);
+                let counts_per_revolution = resolution.counts_per_revolution();
+                let offset = if offset < counts_per_revolution / 2 {
+                    offset as i16
+                } else {
+                    (offset as i32 - counts_per_revolution as i32) as i16
+                };
+                Some(Self::PositionOffsetSetting(offset))
+            }
+            b'M' => Some(Self::MultiturnCounterSetting(i16::from_be_bytes([
+                data[2], data[3],
+            ]))),
+            b'B' => Some(Self::BaudRateSetting(u32::from_be_bytes(
+                data.try_into().ok()?,
+            ))),
+            b'T' => Some(Self::ContinuousResponseSetting {
+                auto_start: data[0] != 0,
+                command: Command::from_byte(data[1])?,
+                period_micros: u16::from_be_bytes([data[2], data[3]]),
+            }),
+            b'S' => Some(Self::ContinuousResponseStart),
+            b'P' => Some(Self::ContinuousResponseStop),
+            b'c' => Some(Self::ConfigurationParametersSave),
+            b'r' => Some(Self::ConfigurationParametersReset),
+            b'A' => Some(Self::SelfCalibrationStart),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let commands = [
+            ProgrammingCommand::PositionOffsetSetting(0),
+            ProgrammingCommand::PositionOffsetSetting(1234),
+            ProgrammingCommand::PositionOffsetSetting(-2048),
+            ProgrammingCommand::PositionOffsetSetting(-1234),
+            ProgrammingCommand::PositionOffsetSetting(2047),
+            ProgrammingCommand::MultiturnCounterSetting(-2),
+            ProgrammingCommand::BaudRateSetting(1_000_000),
+            ProgrammingCommand::ContinuousResponseSetting {
+                auto_start: true,
+                command: Command::PositionRequestAndTemperature,
+                period_micros: 10_000,
+            },
+            ProgrammingCommand::ContinuousResponseStart,
+            ProgrammingCommand::ContinuousResponseStop,
+            ProgrammingCommand::ConfigurationParametersSave,
+            ProgrammingCommand::ConfigurationParametersReset,
+            ProgrammingCommand::SelfCalibrationStart,
+        ];
+
+        for resolution in [Resolution::BITS_12, Resolution::BITS_14] {
+            for command in commands {
+                let data = command.additional_data(resolution).unwrap_or_default();
+                assert_eq!(
+                    ProgrammingCommand::from_bytes(command.to_byte(), &data, resolution),
+                    Some(command)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_offset() {
+        let command = ProgrammingCommand::PositionOffsetSetting(-1);
+        assert_eq!(
+            command.additional_data(Resolution::BITS_14),
+            Some(vec![0x00, 0x00, 0x3F, 0xFF])
+        );
+        assert_eq!(
+            command.additional_data(Resolution::BITS_12),
+            Some(vec![0x00, 0x00, 0x0F, 0xFF])
+        );
+    }
 }
@@ -1,4 +1,8 @@
 use super::Command;
+use crate::{
+    error::{Error, Result},
+    CounterType, Resolution,
+};
 
 pub trait PrefixedResponse {
     fn command() -> Command;
@@ -9,10 +13,80 @@ pub trait PrefixedResponse {
     }
 }
 
+pub trait FrameDecode: AsMut<[u8]> + Sized {
+    fn frame_prefix() -> Option<u8>;
+    fn empty_frame(counter_type: CounterType, resolution: Resolution) -> Self;
+    fn decode(&mut self, bytes: &[u8]) -> Result<()>;
+}
+
+pub(crate) fn decode_frame(buf: &mut [u8], bytes: &[u8], prefix: Option<u8>) -> Result<()> {
+    if bytes.len() != buf.len() {
+        return Err(Error::AsyncSerialInvalidLength {
+            expected: buf.len(),
+            actual: bytes.len(),
+        });
+    }
+    if let (Some(expected), Some(&actual)) = (prefix, bytes.first()) {
+        if actual != expected {
+            return Err(
+                match (Command::from_byte(expected), Command::from_byte(actual)) {
+                    (Some(expected), Some(actual)) => {
+                        Error::AsyncSerialUnexpectedCommand { expected, actual }
+                    }
+                    _ => Error::AsyncSerialInvalidPrefix { expected, actual },
+                },
+            );
+        }
+    }
+    buf.copy_from_slice(bytes);
+    Ok(())
+}
+
+mod dispatch;
 mod position_and_status;
 mod self_calibration_status;
 mod serial_number;
 
+pub use dispatch::Response;
 pub use position_and_status::*;
 pub use self_calibration_status::SelfCalibrationStatus;
 pub use serial_number::SerialNumber;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_frame() {
+        let mut buf = [0; 3];
+
+        decode_frame(&mut buf, &[b'1', 0x12, 0x34], Some(b'1')).unwrap();
+        assert_eq!(buf, [b'1', 0x12, 0x34]);
+
+        assert!(matches!(
+            decode_frame(&mut buf, &[b'1', 0x56], Some(b'1')),
+            Err(Error::AsyncSerialInvalidLength {
+                expected: 3,
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            decode_frame(&mut buf, &[0xff, 0x56, 0x78], Some(b'1')),
+            Err(Error::AsyncSerialInvalidPrefix {
+                expected: b'1',
+                actual: 0xff
+            })
+        ));
+        assert!(matches!(
+            decode_frame(&mut buf, &[b'v', 0x56, 0x78], Some(b'1')),
+            Err(Error::AsyncSerialUnexpectedCommand {
+                expected: Command::PositionRequest,
+                actual: Command::SerialNumber
+            })
+        ));
+        assert_eq!(buf, [b'1', 0x12, 0x34]);
+
+        decode_frame(&mut buf, &[0xff, 0x56, 0x78], None).unwrap();
+        assert_eq!(buf, [0xff, 0x56, 0x78]);
+    }
+}
@@ -0,0 +1,186 @@
+use super::*;
+
+#[derive(Clone)]
+pub enum Response {
+    Position(Position),
+    ShortPosition(ShortPosition),
+    PositionAndDetailedStatus(PositionAndDetailedStatus),
+    PositionAndTemperature(PositionAndTemperature),
+    SerialNumber(SerialNumber),
+    SelfCalibrationStatus(SelfCalibrationStatus),
+}
+
+impl Response {
+    pub fn empty_for_command(
+        command: Command,
+        counter_type: CounterType,
+        resolution: Resolution,
+    ) -> Self {
+        match command {
+            Command::PositionRequest => {
+                Self::Position(Position::with_resolution(counter_type, resolution))
+            }
+            Command::ShortPositionRequest => {
+                Self::ShortPosition(ShortPosition::with_resolution(counter_type, resolution))
+            }
+            Command::PositionRequestAndDetailedStatus => Self::PositionAndDetailedStatus(
+                PositionAndDetailedStatus::with_resolution(counter_type, resolution),
+            ),
+            Command::PositionRequestAndTemperature => Self::PositionAndTemperature(
+                PositionAndTemperature::with_resolution(counter_type, resolution),
+            ),
+            Command::SerialNumber => Self::SerialNumber(SerialNumber::new()),
+            Command::SelfCalibrationStatusRequest => {
+                Self::SelfCalibrationStatus(SelfCalibrationStatus::new())
+            }
+        }
+    }
+
+    pub fn empty_for_prefix(
+        prefix: u8,
+        counter_type: CounterType,
+        resolution: Resolution,
+    ) -> Result<Self> {
+        match Command::from_byte(prefix) {
+            Some(command) if command.response_prefix().is_some() => {
+                Ok(Self::empty_for_command(command, counter_type, resolution))
+            }
+            _ => Err(Error::AsyncSerialUnknownPrefix(prefix)),
+        }
+    }
+
+    pub fn from_bytes(
+        counter_type: CounterType,
+        resolution: Resolution,
+        bytes: &[u8],
+    ) -> Result<Self> {
+        let prefix = *bytes.first().ok_or(Error::AsyncSerialInvalidLength {
+            expected: 1,
+            actual: 0,
+        })?;
+        let mut response = Self::empty_for_prefix(prefix, counter_type, resolution)?;
+        response.try_decode(bytes)?;
+        Ok(response)
+    }
+
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        match self {
+            Self::Position(r) => r.try_decode(bytes),
+            Self::ShortPosition(r) => r.try_decode(bytes),
+            Self::PositionAndDetailedStatus(r) => r.try_decode(bytes),
+            Self::PositionAndTemperature(r) => r.try_decode(bytes),
+            Self::SerialNumber(r) => r.try_decode(bytes),
+            Self::SelfCalibrationStatus(r) => r.try_decode(bytes),
+        }
+    }
+
+    pub fn command(&self) -> Command {
+        match self {
+            Self::Position(_) => Command::PositionRequest,
+            Self::ShortPosition(_) => Command::ShortPositionRequest,
+            Self::PositionAndDetailedStatus(_) => Command::PositionRequestAndDetailedStatus,
+            Self::PositionAndTemperature(_) => Command::PositionRequestAndTemperature,
+            Self::SerialNumber(_) => Command::SerialNumber,
+            Self::SelfCalibrationStatus(_) => Command::SelfCalibrationStatusRequest,
+        }
+    }
+
+    pub fn position_and_status(&self) -> Option<&dyn PositionAndStatus> {
+        match self {
+            Self::Position(r) => Some(r),
+            Self::ShortPosition(r) => Some(r),
+            Self::PositionAndDetailedStatus(r) => Some(r),
+            Self::PositionAndTemperature(r) => Some(r),
+            Self::SerialNumber(_) | Self::SelfCalibrationStatus(_) => None,
+        }
+    }
+}
+
+impl AsMut<[u8]> for Response {
+    fn as_mut(&mut self) -> &mut [u8] {
+        match self {
+            Self::Position(r) => r.as_mut(),
+            Self::ShortPosition(r) => r.as_mut(),
+            Self::PositionAndDetailedStatus(r) => r.as_mut(),
+            Self::PositionAndTemperature(r) => r.as_mut(),
+            Self::SerialNumber(r) => r.as_mut(),
+            Self::SelfCalibrationStatus(r) => r.as_mut(),
+        }
+    }
+}
+
+impl From<Position> for Response {
+    fn from(response: Position) -> Self {
+        Self::Position(response)
+    }
+}
+
+impl From<ShortPosition> for Response {
+    fn from(response: ShortPosition) -> Self {
+        Self::ShortPosition(response)
+    }
+}
+
+impl From<PositionAndDetailedStatus> for Response {
+    fn from(response: PositionAndDetailedStatus) -> Self {
+        Self::PositionAndDetailedStatus(response)
+    }
+}
+
+impl From<PositionAndTemperature> for Response {
+    fn from(response: PositionAndTemperature) -> Self {
+        Self::PositionAndTemperature(response)
+    }
+}
+
+impl From<SerialNumber> for Response {
+    fn from(response: SerialNumber) -> Self {
+        Self::SerialNumber(response)
+    }
+}
+
+impl From<SelfCalibrationStatus> for Response {
+    fn from(response: SelfCalibrationStatus) -> Self {
+        Self::SelfCalibrationStatus(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bytes() {
+        let response = Response::from_bytes(
+            CounterType::MultiTurn,
+            Resolution::default(),
+            &[b'd', 0x00, 0x01, 0x40, 0x03, 0x80],
+        )
+        .unwrap();
+        assert_eq!(
+            response.command(),
+            Command::PositionRequestAndDetailedStatus
+        );
+        let pos = response.position_and_status().unwrap();
+        assert_eq!(pos.multiturn_count(), Some(1));
+        assert_eq!(pos.position(), 4096);
+        match response {
+            Response::PositionAndDetailedStatus(status) => assert!(status.is_signal_too_high()),
+            _ => unreachable!(),
+        }
+
+        let response =
+            Response::from_bytes(CounterType::MultiTurn, Resolution::default(), b"vABC123")
+                .unwrap();
+        assert!(response.position_and_status().is_none());
+
+        assert!(matches!(
+            Response::from_bytes(CounterType::SingleTurn, Resolution::default(), b"3\x40\x03"),
+            Err(Error::AsyncSerialUnknownPrefix(b'3'))
+        ));
+        assert!(matches!(
+            Response::from_bytes(CounterType::SingleTurn, Resolution::default(), b"1\x40"),
+            Err(Error::AsyncSerialInvalidLength { .. })
+        ));
+    }
+}
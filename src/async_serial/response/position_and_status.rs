@@ -1,22 +1,61 @@
 use std::f64::consts::PI;
 
-use crate::{CounterType, COUNTS_PER_REVOLUTION};
+use super::decode_frame;
+use crate::{async_serial::Command, error::Result, AbsolutePosition, CounterType, Resolution};
 
 pub trait PositionAndStatus {
     fn multiturn_count(&self) -> Option<i16>;
     fn position(&self) -> i16;
     fn is_error(&self) -> bool;
     fn is_warning(&self) -> bool;
+    fn resolution(&self) -> Resolution;
+
+    fn absolute_position(&self) -> AbsolutePosition {
+        AbsolutePosition::from_response(self)
+    }
 
     fn angle_rad(&self) -> f64 {
         2.0 * PI
             * (self.multiturn_count().map_or(0.0, |count| count as f64)
-                + self.position() as f64 / COUNTS_PER_REVOLUTION as f64)
+                + self.position() as f64 / self.resolution().counts_per_revolution() as f64)
+    }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct MockPositionAndStatus {
+    pub(crate) multiturn_count: Option<i16>,
+    pub(crate) position: i16,
+    pub(crate) resolution: Resolution,
+}
+
+#[cfg(test)]
+impl PositionAndStatus for MockPositionAndStatus {
+    fn multiturn_count(&self) -> Option<i16> {
+        self.multiturn_count
+    }
+
+    fn position(&self) -> i16 {
+        self.position
+    }
+
+    fn is_error(&self) -> bool {
+        false
+    }
+
+    fn is_warning(&self) -> bool {
+        false
+    }
+
+    fn resolution(&self) -> Resolution {
+        self.resolution
     }
 }
 
+#[derive(Clone)]
 pub struct PositionAndStatusInner {
     buf: Vec<u8>,
+    resolution: Resolution,
     prefix_size: usize,
     multiturn_data_offset: Option<usize>,
     position_data_offset: usize,
@@ -28,7 +67,12 @@ impl PositionAndStatusInner {
     const MULTITURN_DATA_SIZE: usize = 2;
     const POSITION_DATA_SIZE: usize = 2;
 
-    fn new(counter_type: CounterType, prefix_size: usize, postfix_size: usize) -> Self {
+    fn new(
+        counter_type: CounterType,
+        resolution: Resolution,
+        prefix_size: usize,
+        postfix_size: usize,
+    ) -> Self {
         let multiturn_data_offset = match counter_type {
             CounterType::SingleTurn => None,
             CounterType::MultiTurn => Some(prefix_size),
@@ -50,6 +94,7 @@ impl PositionAndStatusInner {
 
         Self {
             buf,
+            resolution,
             prefix_size,
             multiturn_data_offset,
             position_data_offset,
@@ -60,20 +105,13 @@ impl PositionAndStatusInner {
 
     fn multiturn_count(&self) -> Option<i16> {
         let offset = self.multiturn_data_offset?;
-        Some(i16::from_be_bytes(
-            self.buf[offset..offset + Self::MULTITURN_DATA_SIZE]
-                .try_into()
-                .unwrap(),
-        ))
+        Some(i16::from_be_bytes([self.buf[offset], self.buf[offset + 1]]))
     }
 
     fn position(&self) -> i16 {
         let offset = self.position_data_offset;
-        i16::from_be_bytes(
-            self.buf[offset..offset + Self::POSITION_DATA_SIZE]
-                .try_into()
-                .unwrap(),
-        ) >> 2
+        i16::from_be_bytes([self.buf[offset], self.buf[offset + 1]])
+            >> self.resolution.position_shift()
     }
 
     fn is_error(&self) -> bool {
@@ -84,16 +122,17 @@ impl PositionAndStatusInner {
         (self.buf[self.status_data_offset] & 0b00000001) == 0
     }
 
-    fn prefix(&self) -> Option<&[u8]> {
-        if self.prefix_size == 0 {
-            None
-        } else {
-            Some(&self.buf[..self.prefix_size])
-        }
+    fn prefix(&self) -> &[u8] {
+        &self.buf[..self.prefix_size]
+    }
+
+    fn postfix(&self) -> &[u8] {
+        self.postfix_offset
+            .map_or(&[], |offset| &self.buf[offset..])
     }
 
-    fn postfix(&self) -> Option<&[u8]> {
-        self.postfix_offset.map(|offset| &self.buf[offset..])
+    fn try_decode(&mut self, bytes: &[u8], prefix: Option<u8>) -> Result<()> {
+        decode_frame(&mut self.buf, bytes, prefix)
     }
 }
 
@@ -107,6 +146,11 @@ pub trait PositionAndStatusOuter {
     fn inner(&self) -> &PositionAndStatusInner;
 }
 
+pub trait PositionResponse: PositionAndStatus + AsMut<[u8]> {
+    fn request_command() -> Command;
+    fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self;
+}
+
 impl<T: PositionAndStatusOuter> PositionAndStatus for T {
     fn multiturn_count(&self) -> Option<i16> {
         self.inner().multiturn_count()
@@ -123,6 +167,10 @@ impl<T: PositionAndStatusOuter> PositionAndStatus for T {
     fn is_warning(&self) -> bool {
         self.inner().is_warning()
     }
+
+    fn resolution(&self) -> Resolution {
+        self.inner().resolution
+    }
 }
 
 mod position;
@@ -147,7 +195,8 @@ mod tests {
     fn test_singleturn_position() {
         use std::io::Write;
 
-        let mut pos = PositionAndStatusInner::new(CounterType::SingleTurn, 0, 0);
+        let mut pos =
+            PositionAndStatusInner::new(CounterType::SingleTurn, Resolution::default(), 0, 0);
 
         pos.as_mut().write_all(&[0b00000000, 0b000000_00]).unwrap();
         assert_eq!(pos.position(), 0);
@@ -206,27 +255,23 @@ mod tests {
         assert_eq!(pos.position(), 605);
     }
 
-    struct MockPositionAndStatus {
-        multiturn_count: Option<i16>,
-        position: i16,
-    }
-
-    impl PositionAndStatus for MockPositionAndStatus {
-        fn multiturn_count(&self) -> Option<i16> {
-            self.multiturn_count
-        }
-
-        fn position(&self) -> i16 {
-            self.position
-        }
+    #[test]
+    fn test_12bit_position() {
+        use std::io::Write;
 
-        fn is_error(&self) -> bool {
-            false
-        }
+        let mut pos =
+            PositionAndStatusInner::new(CounterType::SingleTurn, Resolution::BITS_12, 0, 0);
 
-        fn is_warning(&self) -> bool {
-            false
-        }
+        pos.as_mut().write_all(&[0b00000000, 0b0001_00_11]).unwrap();
+        assert_eq!(pos.position(), 1);
+        assert!(!pos.is_error());
+        assert!(!pos.is_warning());
+        pos.as_mut().write_all(&[0b01111111, 0b1111_00_00]).unwrap();
+        assert_eq!(pos.position(), 2047);
+        pos.as_mut().write_all(&[0b10000000, 0b0000_00_00]).unwrap();
+        assert_eq!(pos.position(), -2048);
+        pos.as_mut().write_all(&[0b11111111, 0b1111_00_00]).unwrap();
+        assert_eq!(pos.position(), -1);
     }
 
     #[test]
@@ -234,39 +279,58 @@ mod tests {
         use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 
         let pos = MockPositionAndStatus {
-            multiturn_count: None,
             position: 0,
+            ..Default::default()
         };
         assert_approx_eq!(pos.angle_rad(), 0.0);
 
         let pos = MockPositionAndStatus {
-            multiturn_count: None,
             position: 2048,
+            ..Default::default()
         };
         assert_approx_eq!(pos.angle_rad(), FRAC_PI_4);
 
         let pos = MockPositionAndStatus {
-            multiturn_count: None,
             position: 4096,
+            ..Default::default()
         };
         assert_approx_eq!(pos.angle_rad(), FRAC_PI_2);
 
         let pos = MockPositionAndStatus {
-            multiturn_count: None,
             position: -4096,
+            ..Default::default()
         };
         assert_approx_eq!(pos.angle_rad(), -FRAC_PI_2);
 
         let pos = MockPositionAndStatus {
-            multiturn_count: None,
             position: -8192,
+            ..Default::default()
         };
         assert_approx_eq!(pos.angle_rad(), -PI);
 
         let pos = MockPositionAndStatus {
-            multiturn_count: None,
             position: -2048,
+            ..Default::default()
         };
         assert_approx_eq!(pos.angle_rad(), -FRAC_PI_4);
     }
+
+    #[test]
+    fn test_12bit_angle() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+
+        let pos = MockPositionAndStatus {
+            position: 1024,
+            resolution: Resolution::BITS_12,
+            ..Default::default()
+        };
+        assert_approx_eq!(pos.angle_rad(), FRAC_PI_2);
+
+        let pos = MockPositionAndStatus {
+            multiturn_count: Some(-1),
+            position: -2048,
+            resolution: Resolution::BITS_12,
+        };
+        assert_approx_eq!(pos.angle_rad(), -3.0 * PI);
+    }
 }
@@ -1,6 +1,10 @@
 use super::*;
-use crate::async_serial::{response::PrefixedResponse, Command};
+use crate::async_serial::{
+    response::{FrameDecode, PrefixedResponse},
+    Command,
+};
 
+#[derive(Clone)]
 pub struct Position {
     counter_type: CounterType,
     inner: PositionAndStatusInner,
@@ -11,19 +15,43 @@ impl Position {
     const PREFIX_SIZE: usize = 1;
 
     pub fn new(counter_type: CounterType) -> Self {
+        Self::with_resolution(counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
         Self {
             counter_type,
             inner: PositionAndStatusInner::new(
                 counter_type,
+                resolution,
                 Self::PREFIX_SIZE,
                 Self::ADDITIONAL_DATA_SIZE,
             ),
         }
     }
 
+    pub fn from_bytes(counter_type: CounterType, bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_resolution(counter_type, Resolution::default(), bytes)
+    }
+
+    pub fn from_bytes_with_resolution(
+        counter_type: CounterType,
+        resolution: Resolution,
+        bytes: &[u8],
+    ) -> Result<Self> {
+        let mut response = Self::with_resolution(counter_type, resolution);
+        response.try_decode(bytes)?;
+        Ok(response)
+    }
+
     pub fn counter_type(&self) -> CounterType {
         self.counter_type
     }
+
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.inner
+            .try_decode(bytes, Command::PositionRequest.response_prefix())
+    }
 }
 
 impl PositionAndStatusOuter for Position {
@@ -38,7 +66,31 @@ impl PrefixedResponse for Position {
     }
 
     fn prefix(&self) -> u8 {
-        self.inner.prefix().unwrap()[0]
+        self.inner.prefix()[0]
+    }
+}
+
+impl PositionResponse for Position {
+    fn request_command() -> Command {
+        Command::PositionRequest
+    }
+
+    fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+}
+
+impl FrameDecode for Position {
+    fn frame_prefix() -> Option<u8> {
+        Command::PositionRequest.response_prefix()
+    }
+
+    fn empty_frame(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.try_decode(bytes)
     }
 }
 
@@ -101,4 +153,21 @@ mod tests {
             .unwrap();
         assert_eq!(pos.position(), 605);
     }
+
+    #[test]
+    fn test_from_bytes_with_resolution() {
+        let bytes = [b'1', 0b01111111, 0b1111_00_00];
+
+        let pos = Position::from_bytes_with_resolution(
+            CounterType::SingleTurn,
+            Resolution::BITS_12,
+            &bytes,
+        )
+        .unwrap();
+        assert_eq!(pos.resolution(), Resolution::BITS_12);
+        assert_eq!(pos.position(), 2047);
+
+        let pos = Position::from_bytes(CounterType::SingleTurn, &bytes).unwrap();
+        assert_eq!(pos.position(), 8188);
+    }
 }
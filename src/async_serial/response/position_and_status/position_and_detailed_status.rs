@@ -1,6 +1,10 @@
 use super::*;
-use crate::async_serial::{response::PrefixedResponse, Command};
+use crate::async_serial::{
+    response::{FrameDecode, PrefixedResponse},
+    Command,
+};
 
+#[derive(Clone)]
 pub struct PositionAndDetailedStatus {
     counter_type: CounterType,
     inner: PositionAndStatusInner,
@@ -11,22 +15,48 @@ impl PositionAndDetailedStatus {
     const PREFIX_SIZE: usize = 1;
 
     pub fn new(counter_type: CounterType) -> Self {
+        Self::with_resolution(counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
         Self {
             counter_type,
             inner: PositionAndStatusInner::new(
                 counter_type,
+                resolution,
                 Self::PREFIX_SIZE,
                 Self::DETAILED_STATUS_DATA_SIZE,
             ),
         }
     }
 
+    pub fn from_bytes(counter_type: CounterType, bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_resolution(counter_type, Resolution::default(), bytes)
+    }
+
+    pub fn from_bytes_with_resolution(
+        counter_type: CounterType,
+        resolution: Resolution,
+        bytes: &[u8],
+    ) -> Result<Self> {
+        let mut response = Self::with_resolution(counter_type, resolution);
+        response.try_decode(bytes)?;
+        Ok(response)
+    }
+
     pub fn counter_type(&self) -> CounterType {
         self.counter_type
     }
 
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.inner.try_decode(
+            bytes,
+            Command::PositionRequestAndDetailedStatus.response_prefix(),
+        )
+    }
+
     fn detailed_status(&self) -> u8 {
-        self.inner.postfix().unwrap()[0]
+        self.inner.postfix()[0]
     }
 
     pub fn is_signal_too_high(&self) -> bool {
@@ -62,7 +92,31 @@ impl PrefixedResponse for PositionAndDetailedStatus {
     }
 
     fn prefix(&self) -> u8 {
-        self.inner.prefix().unwrap()[0]
+        self.inner.prefix()[0]
+    }
+}
+
+impl PositionResponse for PositionAndDetailedStatus {
+    fn request_command() -> Command {
+        Command::PositionRequestAndDetailedStatus
+    }
+
+    fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+}
+
+impl FrameDecode for PositionAndDetailedStatus {
+    fn frame_prefix() -> Option<u8> {
+        Command::PositionRequestAndDetailedStatus.response_prefix()
+    }
+
+    fn empty_frame(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.try_decode(bytes)
     }
 }
 
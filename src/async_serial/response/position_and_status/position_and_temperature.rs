@@ -1,6 +1,10 @@
 use super::*;
-use crate::async_serial::{response::PrefixedResponse, Command};
+use crate::async_serial::{
+    response::{FrameDecode, PrefixedResponse},
+    Command,
+};
 
+#[derive(Clone)]
 pub struct PositionAndTemperature {
     counter_type: CounterType,
     inner: PositionAndStatusInner,
@@ -11,22 +15,49 @@ impl PositionAndTemperature {
     const TEMPERATURE_DATA_SIZE: usize = 2;
 
     pub fn new(counter_type: CounterType) -> Self {
+        Self::with_resolution(counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
         Self {
             counter_type,
             inner: PositionAndStatusInner::new(
                 counter_type,
+                resolution,
                 Self::PREFIX_SIZE,
                 Self::TEMPERATURE_DATA_SIZE,
             ),
         }
     }
 
+    pub fn from_bytes(counter_type: CounterType, bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_resolution(counter_type, Resolution::default(), bytes)
+    }
+
+    pub fn from_bytes_with_resolution(
+        counter_type: CounterType,
+        resolution: Resolution,
+        bytes: &[u8],
+    ) -> Result<Self> {
+        let mut response = Self::with_resolution(counter_type, resolution);
+        response.try_decode(bytes)?;
+        Ok(response)
+    }
+
     pub fn counter_type(&self) -> CounterType {
         self.counter_type
     }
 
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.inner.try_decode(
+            bytes,
+            Command::PositionRequestAndTemperature.response_prefix(),
+        )
+    }
+
     pub fn temperature(&self) -> f64 {
-        i16::from_be_bytes(self.inner.postfix().unwrap().try_into().unwrap()) as f64 / 10.0
+        let postfix = self.inner.postfix();
+        i16::from_be_bytes([postfix[0], postfix[1]]) as f64 / 10.0
     }
 }
 
@@ -42,7 +73,31 @@ impl PrefixedResponse for PositionAndTemperature {
     }
 
     fn prefix(&self) -> u8 {
-        self.inner.prefix().unwrap()[0]
+        self.inner.prefix()[0]
+    }
+}
+
+impl PositionResponse for PositionAndTemperature {
+    fn request_command() -> Command {
+        Command::PositionRequestAndTemperature
+    }
+
+    fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+}
+
+impl FrameDecode for PositionAndTemperature {
+    fn frame_prefix() -> Option<u8> {
+        Command::PositionRequestAndTemperature.response_prefix()
+    }
+
+    fn empty_frame(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.try_decode(bytes)
     }
 }
 
@@ -147,4 +202,32 @@ mod tests {
             .unwrap();
         assert_approx_eq!(result.temperature(), -273.2);
     }
+
+    #[test]
+    fn test_try_decode() {
+        use crate::error::Error;
+
+        let mut pos = PositionAndTemperature::new(CounterType::MultiTurn);
+        pos.try_decode(&[b't', 0xff, 0xff, 0x40, 0x03, 0x01, 0x2c])
+            .unwrap();
+        assert_eq!(pos.multiturn_count(), Some(-1));
+        assert_eq!(pos.position(), 4096);
+        assert_approx_eq!(pos.temperature(), 30.0);
+
+        assert!(matches!(
+            pos.try_decode(&[b't', 0x40, 0x03, 0x01, 0x2c]),
+            Err(Error::AsyncSerialInvalidLength {
+                expected: 7,
+                actual: 5
+            })
+        ));
+        assert!(matches!(
+            pos.try_decode(&[b'd', 0xff, 0xff, 0x40, 0x03, 0x01, 0x2c]),
+            Err(Error::AsyncSerialUnexpectedCommand {
+                expected: Command::PositionRequestAndTemperature,
+                actual: Command::PositionRequestAndDetailedStatus
+            })
+        ));
+        assert_eq!(pos.position(), 4096);
+    }
 }
@@ -1,5 +1,7 @@
 use super::*;
+use crate::async_serial::{response::FrameDecode, Command};
 
+#[derive(Clone)]
 pub struct ShortPosition {
     counter_type: CounterType,
     inner: PositionAndStatusInner,
@@ -10,19 +12,43 @@ impl ShortPosition {
     const PREFIX_SIZE: usize = 0;
 
     pub fn new(counter_type: CounterType) -> Self {
+        Self::with_resolution(counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
         Self {
             counter_type,
             inner: PositionAndStatusInner::new(
                 counter_type,
+                resolution,
                 Self::PREFIX_SIZE,
                 Self::ADDITIONAL_DATA_SIZE,
             ),
         }
     }
 
+    pub fn from_bytes(counter_type: CounterType, bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_with_resolution(counter_type, Resolution::default(), bytes)
+    }
+
+    pub fn from_bytes_with_resolution(
+        counter_type: CounterType,
+        resolution: Resolution,
+        bytes: &[u8],
+    ) -> Result<Self> {
+        let mut response = Self::with_resolution(counter_type, resolution);
+        response.try_decode(bytes)?;
+        Ok(response)
+    }
+
     pub fn counter_type(&self) -> CounterType {
         self.counter_type
     }
+
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.inner
+            .try_decode(bytes, Command::ShortPositionRequest.response_prefix())
+    }
 }
 
 impl PositionAndStatusOuter for ShortPosition {
@@ -31,6 +57,30 @@ impl PositionAndStatusOuter for ShortPosition {
     }
 }
 
+impl PositionResponse for ShortPosition {
+    fn request_command() -> Command {
+        Command::ShortPositionRequest
+    }
+
+    fn with_resolution(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+}
+
+impl FrameDecode for ShortPosition {
+    fn frame_prefix() -> Option<u8> {
+        Command::ShortPositionRequest.response_prefix()
+    }
+
+    fn empty_frame(counter_type: CounterType, resolution: Resolution) -> Self {
+        Self::with_resolution(counter_type, resolution)
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.try_decode(bytes)
+    }
+}
+
 impl AsMut<[u8]> for ShortPosition {
     fn as_mut(&mut self) -> &mut [u8] {
         self.inner.as_mut()
@@ -1,8 +1,12 @@
 use super::*;
+use crate::{
+    error::{Error, Result},
+    CounterType, Resolution,
+};
 
 const SELF_CALIBRATION_STATUS_SIZE: usize = 1;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SelfCalibrationStatus {
     buf: [u8; SELF_CALIBRATION_STATUS_SIZE + 1],
 }
@@ -14,6 +18,14 @@ impl SelfCalibrationStatus {
         }
     }
 
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes)
+    }
+
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        decode_frame(&mut self.buf, bytes, Some(Self::command().to_byte()))
+    }
+
     fn status_byte(&self) -> u8 {
         self.buf[1]
     }
@@ -31,6 +43,16 @@ impl SelfCalibrationStatus {
     }
 }
 
+impl TryFrom<&[u8]> for SelfCalibrationStatus {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let mut status = Self::new();
+        status.try_decode(bytes)?;
+        Ok(status)
+    }
+}
+
 impl PrefixedResponse for SelfCalibrationStatus {
     fn command() -> Command {
         Command::SelfCalibrationStatusRequest
@@ -41,6 +63,20 @@ impl PrefixedResponse for SelfCalibrationStatus {
     }
 }
 
+impl FrameDecode for SelfCalibrationStatus {
+    fn frame_prefix() -> Option<u8> {
+        Some(Command::SelfCalibrationStatusRequest.to_byte())
+    }
+
+    fn empty_frame(_counter_type: CounterType, _resolution: Resolution) -> Self {
+        Self::new()
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.try_decode(bytes)
+    }
+}
+
 impl AsMut<[u8]> for SelfCalibrationStatus {
     fn as_mut(&mut self) -> &mut [u8] {
         &mut self.buf
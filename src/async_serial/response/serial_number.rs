@@ -1,10 +1,14 @@
 use std::fmt;
 
 use super::*;
+use crate::{
+    error::{Error, Result},
+    CounterType, Resolution,
+};
 
 const SERIAL_NUMBER_LENGTH: usize = 6;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct SerialNumber {
     buf: [u8; SERIAL_NUMBER_LENGTH + 1],
 }
@@ -16,8 +20,29 @@ impl SerialNumber {
         }
     }
 
-    pub fn as_str(&self) -> &str {
-        std::str::from_utf8(&self.buf[1..]).unwrap()
+    pub fn as_str(&self) -> Result<&str> {
+        Ok(std::str::from_utf8(&self.buf[1..])?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::try_from(bytes)
+    }
+
+    pub fn try_decode(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.len() == self.buf.len() {
+            std::str::from_utf8(&bytes[1..])?;
+        }
+        decode_frame(&mut self.buf, bytes, Some(Self::command().to_byte()))
+    }
+}
+
+impl TryFrom<&[u8]> for SerialNumber {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        let mut serial_number = Self::new();
+        serial_number.try_decode(bytes)?;
+        Ok(serial_number)
     }
 }
 
@@ -31,6 +56,20 @@ impl PrefixedResponse for SerialNumber {
     }
 }
 
+impl FrameDecode for SerialNumber {
+    fn frame_prefix() -> Option<u8> {
+        Some(Command::SerialNumber.to_byte())
+    }
+
+    fn empty_frame(_counter_type: CounterType, _resolution: Resolution) -> Self {
+        Self::new()
+    }
+
+    fn decode(&mut self, bytes: &[u8]) -> Result<()> {
+        self.try_decode(bytes)
+    }
+}
+
 impl AsMut<[u8]> for SerialNumber {
     fn as_mut(&mut self) -> &mut [u8] {
         &mut self.buf
@@ -39,6 +78,47 @@ impl AsMut<[u8]> for SerialNumber {
 
 impl fmt::Display for SerialNumber {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.as_str())
+        write!(f, "{}", String::from_utf8_lossy(&self.buf[1..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from() {
+        let serial_number = SerialNumber::try_from(&b"vABC123"[..]).unwrap();
+        assert_eq!(serial_number.as_str().unwrap(), "ABC123");
+        assert_eq!(serial_number.to_string(), "ABC123");
+
+        assert!(matches!(
+            SerialNumber::try_from(&b"vABC"[..]),
+            Err(Error::AsyncSerialInvalidLength {
+                expected: 7,
+                actual: 4
+            })
+        ));
+        assert!(matches!(
+            SerialNumber::try_from(&[b'v', b'A', 0xff, b'C', b'1', b'2', b'3'][..]),
+            Err(Error::AsyncSerialInvalidUtf8(_))
+        ));
+        assert!(matches!(
+            SerialNumber::try_from(&b"1ABC123"[..]),
+            Err(Error::AsyncSerialUnexpectedCommand { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut serial_number = SerialNumber::new();
+        serial_number
+            .as_mut()
+            .copy_from_slice(&[b'v', b'A', 0xff, b'C', b'1', b'2', b'3']);
+        assert!(matches!(
+            serial_number.as_str(),
+            Err(Error::AsyncSerialInvalidUtf8(_))
+        ));
+        assert_eq!(serial_number.to_string(), "A\u{FFFD}C123");
     }
 }
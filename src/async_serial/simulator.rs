@@ -0,0 +1,731 @@
+use std::{
+    f64::consts::PI,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use super::{
+    programming_command::PROGRAMMING_UNLOCKING_SEQUENCE, Command, Encoder, MemoryPipe, Port,
+    ProgrammingCommand, Transport,
+};
+use crate::{
+    error::{Error, Result},
+    CounterType, Resolution,
+};
+
+const SERIAL_NUMBER_LENGTH: usize = 6;
+const DEFAULT_SERIAL_NUMBER: &str = "SIM001";
+const DEFAULT_TEMPERATURE: f64 = 25.0;
+const DEFAULT_BAUD_RATE: u32 = 115_200;
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(100);
+const DEFAULT_SELF_CALIBRATION_TIMEOUT: Duration = Duration::from_secs(10);
+const SELF_CALIBRATION_TIMEOUT_BIT: u8 = 0b00000100;
+const SELF_CALIBRATION_OUT_OF_RANGE_BIT: u8 = 0b00001000;
+const SELF_CALIBRATION_COUNTER_MASK: u8 = 0b00000011;
+#[cfg(feature = "tokio")]
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+#[cfg(feature = "tokio")]
+const DUPLEX_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContinuousResponseConfig {
+    pub auto_start: bool,
+    pub command: Command,
+    pub period_micros: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Parameters {
+    position_offset: i16,
+    multiturn_offset: i64,
+    baud_rate: u32,
+    continuous_response: Option<ContinuousResponseConfig>,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            position_offset: 0,
+            multiturn_offset: 0,
+            baud_rate: DEFAULT_BAUD_RATE,
+            continuous_response: None,
+        }
+    }
+}
+
+enum ProgrammingState {
+    Idle,
+    Unlocking(usize),
+    AwaitingCommand,
+    AwaitingData { command: u8, data: Vec<u8> },
+}
+
+pub struct SimulatedEncoder {
+    counter_type: CounterType,
+    resolution: Resolution,
+    serial_number: [u8; SERIAL_NUMBER_LENGTH],
+    angle_profile: Box<dyn FnMut(Duration) -> f64 + Send>,
+    started_at: Instant,
+    temperature: f64,
+    is_error: bool,
+    is_warning: bool,
+    detailed_status: u8,
+    self_calibration_status: u8,
+    self_calibration: Option<(Instant, f64)>,
+    self_calibration_timeout: Duration,
+    self_calibration_out_of_range: bool,
+    parameters: Parameters,
+    saved_parameters: Parameters,
+    programming_state: ProgrammingState,
+    next_continuous_response: Option<Instant>,
+    noise: Vec<u8>,
+    write_protected: bool,
+    drop_final_echo: bool,
+}
+
+impl SimulatedEncoder {
+    pub fn new(counter_type: CounterType) -> Self {
+        let mut serial_number = [0; SERIAL_NUMBER_LENGTH];
+        serial_number.copy_from_slice(DEFAULT_SERIAL_NUMBER.as_bytes());
+
+        Self {
+            counter_type,
+            resolution: Resolution::default(),
+            serial_number,
+            angle_profile: Box::new(|_| 0.0),
+            started_at: Instant::now(),
+            temperature: DEFAULT_TEMPERATURE,
+            is_error: false,
+            is_warning: false,
+            detailed_status: 0,
+            self_calibration_status: 0,
+            self_calibration: None,
+            self_calibration_timeout: DEFAULT_SELF_CALIBRATION_TIMEOUT,
+            self_calibration_out_of_range: false,
+            parameters: Parameters::default(),
+            saved_parameters: Parameters::default(),
+            programming_state: ProgrammingState::Idle,
+            next_continuous_response: None,
+            noise: Vec::new(),
+            write_protected: false,
+            drop_final_echo: false,
+        }
+    }
+
+    pub fn counter_type(&self) -> CounterType {
+        self.counter_type
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    pub fn set_serial_number(&mut self, serial_number: &str) -> Result<()> {
+        if serial_number.len() != SERIAL_NUMBER_LENGTH {
+            return Err(Error::SimulatorInvalidSerialNumber(
+                serial_number.to_owned(),
+            ));
+        }
+        self.serial_number.copy_from_slice(serial_number.as_bytes());
+        Ok(())
+    }
+
+    pub fn set_angle(&mut self, angle_rad: f64) {
+        self.set_angle_profile(move |_| angle_rad);
+    }
+
+    pub fn set_angle_profile(&mut self, profile: impl FnMut(Duration) -> f64 + Send + 'static) {
+        self.angle_profile = Box::new(profile);
+        self.started_at = Instant::now();
+    }
+
+    pub fn set_temperature(&mut self, temperature: f64) {
+        self.temperature = temperature;
+    }
+
+    pub fn set_error(&mut self, is_error: bool) {
+        self.is_error = is_error;
+    }
+
+    pub fn set_warning(&mut self, is_warning: bool) {
+        self.is_warning = is_warning;
+    }
+
+    pub fn set_detailed_status(&mut self, detailed_status: u8) {
+        self.detailed_status = detailed_status;
+    }
+
+    pub fn set_self_calibration_status(&mut self, status: u8) {
+        self.self_calibration_status = status;
+    }
+
+    pub fn set_self_calibration_timeout(&mut self, timeout: Duration) {
+        self.self_calibration_timeout = timeout;
+    }
+
+    pub fn set_self_calibration_out_of_range(&mut self, out_of_range: bool) {
+        self.self_calibration_out_of_range = out_of_range;
+    }
+
+    pub fn is_self_calibrating(&self) -> bool {
+        self.self_calibration.is_some()
+    }
+
+    pub fn queue_noise(&mut self, noise: &[u8]) {
+        self.noise.extend(noise);
+    }
+
+    pub fn position_offset(&self) -> i16 {
+        self.parameters.position_offset
+    }
+
+    pub fn baud_rate(&self) -> u32 {
+        self.parameters.baud_rate
+    }
+
+    pub fn continuous_response(&self) -> Option<ContinuousResponseConfig> {
+        self.parameters.continuous_response
+    }
+
+    pub fn is_continuous_response_running(&self) -> bool {
+        self.next_continuous_response.is_some()
+    }
+
+    pub fn set_baud_rate(&mut self, baud_rate: u32) {
+        assert!(
+            ProgrammingCommand::is_supported_baud_rate(baud_rate),
+            "unsupported baud rate: {baud_rate}"
+        );
+        self.parameters.baud_rate = baud_rate;
+        self.saved_parameters.baud_rate = baud_rate;
+    }
+
+    pub fn set_write_protected(&mut self, write_protected: bool) {
+        self.write_protected = write_protected;
+    }
+
+    pub fn set_drop_final_echo(&mut self, drop_final_echo: bool) {
+        self.drop_final_echo = drop_final_echo;
+    }
+
+    pub fn power_cycle(&mut self) {
+        self.parameters = self.saved_parameters;
+        self.programming_state = ProgrammingState::Idle;
+        self.next_continuous_response = match self.parameters.continuous_response {
+            Some(config) if config.auto_start => Some(Instant::now()),
+            _ => None,
+        };
+    }
+
+    fn angle(&mut self) -> f64 {
+        (self.angle_profile)(self.started_at.elapsed())
+    }
+
+    fn update_self_calibration(&mut self) {
+        let Some((started_at, start_angle)) = self.self_calibration else {
+            return;
+        };
+        let counter = self.self_calibration_status & SELF_CALIBRATION_COUNTER_MASK;
+        self.self_calibration_status = if self.self_calibration_out_of_range {
+            counter | SELF_CALIBRATION_OUT_OF_RANGE_BIT
+        } else if (self.angle() - start_angle).abs() >= 2.0 * PI {
+            (counter + 1) & SELF_CALIBRATION_COUNTER_MASK
+        } else if started_at.elapsed() >= self.self_calibration_timeout {
+            counter | SELF_CALIBRATION_TIMEOUT_BIT
+        } else {
+            return;
+        };
+        self.self_calibration = None;
+    }
+
+    fn counts(&mut self) -> (i16, i16) {
+        let angle = self.angle();
+        let counts_per_revolution = self.resolution.counts_per_revolution() as i64;
+        let raw = (angle / (2.0 * PI) * counts_per_revolution as f64).round() as i64
+            - self.parameters.position_offset as i64;
+        let turns = (raw + counts_per_revolution / 2).div_euclid(counts_per_revolution);
+        let position = raw - turns * counts_per_revolution;
+        (
+            (turns + self.parameters.multiturn_offset) as i16,
+            position as i16,
+        )
+    }
+
+    fn position_and_status(&mut self) -> Vec<u8> {
+        let (turns, position) = self.counts();
+        let status = u16::from(!self.is_error) << 1 | u16::from(!self.is_warning);
+
+        let mut data = Vec::new();
+        if self.counter_type == CounterType::MultiTurn {
+            data.extend(turns.to_be_bytes());
+        }
+        data.extend(
+            (((position << self.resolution.position_shift()) as u16) | status).to_be_bytes(),
+        );
+        data
+    }
+
+    fn response(&mut self, command: Command) -> Vec<u8> {
+        let mut data = std::mem::take(&mut self.noise);
+        if command != Command::ShortPositionRequest {
+            data.push(command.to_byte());
+        }
+        match command {
+            Command::PositionRequest | Command::ShortPositionRequest => {
+                data.extend(self.position_and_status());
+            }
+            Command::PositionRequestAndDetailedStatus => {
+                data.extend(self.position_and_status());
+                data.push(self.detailed_status);
+            }
+            Command::PositionRequestAndTemperature => {
+                data.extend(self.position_and_status());
+                data.extend(((self.temperature * 10.0).round() as i16).to_be_bytes());
+            }
+            Command::SerialNumber => data.extend(self.serial_number),
+            Command::SelfCalibrationStatusRequest => {
+                self.update_self_calibration();
+                data.push(self.self_calibration_status);
+            }
+        }
+        data
+    }
+
+    fn apply_and_echo(&mut self, command: ProgrammingCommand, byte: u8) -> Vec<u8> {
+        self.apply(command);
+        if self.drop_final_echo {
+            Vec::new()
+        } else {
+            vec![byte]
+        }
+    }
+
+    fn apply(&mut self, command: ProgrammingCommand) {
+        if self.write_protected {
+            return;
+        }
+        match command {
+            ProgrammingCommand::PositionOffsetSetting(offset) => {
+                self.parameters.position_offset = offset;
+            }
+            ProgrammingCommand::MultiturnCounterSetting(count) => {
+                self.parameters.multiturn_offset = 0;
+                let (turns, _) = self.counts();
+                self.parameters.multiturn_offset = count as i64 - turns as i64;
+            }
+            ProgrammingCommand::BaudRateSetting(baud_rate) => {
+                if ProgrammingCommand::is_supported_baud_rate(baud_rate) {
+                    self.parameters.baud_rate = baud_rate;
+                }
+            }
+            ProgrammingCommand::ContinuousResponseSetting {
+                auto_start,
+                command,
+                period_micros,
+            } => {
+                self.parameters.continuous_response = Some(ContinuousResponseConfig {
+                    auto_start,
+                    command,
+                    period_micros,
+                });
+            }
+            ProgrammingCommand::ContinuousResponseStart => {
+                if self.parameters.continuous_response.is_some() {
+                    self.next_continuous_response = Some(Instant::now());
+                }
+            }
+            ProgrammingCommand::ContinuousResponseStop => self.next_continuous_response = None,
+            ProgrammingCommand::ConfigurationParametersSave => {
+                self.saved_parameters = self.parameters;
+            }
+            ProgrammingCommand::ConfigurationParametersReset => {
+                self.parameters = Parameters::default();
+            }
+            ProgrammingCommand::SelfCalibrationStart => {
+                self.self_calibration_status &= SELF_CALIBRATION_COUNTER_MASK;
+                self.self_calibration = Some((Instant::now(), self.angle()));
+            }
+        }
+    }
+
+    pub fn handle_byte(&mut self, byte: u8) -> Vec<u8> {
+        match std::mem::replace(&mut self.programming_state, ProgrammingState::Idle) {
+            ProgrammingState::Idle => {
+                if byte == PROGRAMMING_UNLOCKING_SEQUENCE[0] {
+                    self.programming_state = ProgrammingState::Unlocking(1);
+                    vec![byte]
+                } else {
+                    Command::from_byte(byte).map_or_else(Vec::new, |c| self.response(c))
+                }
+            }
+            ProgrammingState::Unlocking(matched) => {
+                if byte != PROGRAMMING_UNLOCKING_SEQUENCE[matched] {
+                    return self.handle_byte(byte);
+                }
+                self.programming_state = if matched + 1 == PROGRAMMING_UNLOCKING_SEQUENCE.len() {
+                    ProgrammingState::AwaitingCommand
+                } else {
+                    ProgrammingState::Unlocking(matched + 1)
+                };
+                vec![byte]
+            }
+            ProgrammingState::AwaitingCommand => {
+                match ProgrammingCommand::additional_data_size(byte) {
+                    Some(0) => {
+                        if let Some(command) =
+                            ProgrammingCommand::from_bytes(byte, &[], self.resolution)
+                        {
+                            return self.apply_and_echo(command, byte);
+                        }
+                    }
+                    Some(_) => {
+                        self.programming_state = ProgrammingState::AwaitingData {
+                            command: byte,
+                            data: Vec::new(),
+                        }
+                    }
+                    None => {}
+                }
+                vec![byte]
+            }
+            ProgrammingState::AwaitingData { command, mut data } => {
+                data.push(byte);
+                if Some(data.len()) == ProgrammingCommand::additional_data_size(command) {
+                    if let Some(command) =
+                        ProgrammingCommand::from_bytes(command, &data, self.resolution)
+                    {
+                        return self.apply_and_echo(command, byte);
+                    }
+                } else {
+                    self.programming_state = ProgrammingState::AwaitingData { command, data };
+                }
+                vec![byte]
+            }
+        }
+    }
+
+    pub fn poll_continuous_response(&mut self, now: Instant) -> Option<Vec<u8>> {
+        let due = self.next_continuous_response?;
+        let config = self.parameters.continuous_response?;
+        if now < due {
+            return None;
+        }
+        let period = Duration::from_micros(config.period_micros.into());
+        self.next_continuous_response = Some((due + period).max(now));
+        Some(self.response(config.command))
+    }
+
+    fn time_until_next_response(&self, now: Instant) -> Duration {
+        self.next_continuous_response
+            .map_or(POLL_INTERVAL, |due| {
+                due.saturating_duration_since(now).min(POLL_INTERVAL)
+            })
+            .max(Duration::from_micros(100))
+    }
+
+    fn spawn(
+        self,
+        run: impl FnOnce(Arc<Mutex<Self>>, Arc<AtomicBool>) + Send + 'static,
+    ) -> SimulatorHandle {
+        let encoder = Arc::new(Mutex::new(self));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread = {
+            let encoder = encoder.clone();
+            let stop = stop.clone();
+            std::thread::spawn(move || run(encoder, stop))
+        };
+
+        SimulatorHandle {
+            encoder,
+            stop,
+            thread: Some(thread),
+        }
+    }
+
+    pub fn run<T: Transport + Send + 'static>(self, mut transport: T) -> SimulatorHandle {
+        self.spawn(move |encoder, stop| {
+            let mut buf = [0; 1];
+            let mut baud_rate = None;
+            while !stop.load(Ordering::Relaxed) {
+                let current = encoder.lock().unwrap().baud_rate();
+                if baud_rate != Some(current) {
+                    if transport.set_baud_rate(current).is_err() {
+                        return;
+                    }
+                    baud_rate = Some(current);
+                }
+
+                let timeout = encoder
+                    .lock()
+                    .unwrap()
+                    .time_until_next_response(Instant::now());
+                if transport.set_timeout(timeout).is_err() {
+                    return;
+                }
+
+                let output = match transport.read_exact(&mut buf) {
+                    Ok(()) => encoder.lock().unwrap().handle_byte(buf[0]),
+                    Err(e) if e.kind() == io::ErrorKind::TimedOut => Vec::new(),
+                    Err(_) => return,
+                };
+                if !output.is_empty() && transport.write_all(&output).is_err() {
+                    return;
+                }
+
+                let frame = encoder
+                    .lock()
+                    .unwrap()
+                    .poll_continuous_response(Instant::now());
+                if let Some(frame) = frame {
+                    if transport.write_all(&frame).is_err() {
+                        return;
+                    }
+                }
+            }
+        })
+    }
+
+    pub fn connect(self) -> (Port<MemoryPipe>, SimulatorHandle) {
+        let (mut host, device) = MemoryPipe::pair();
+        host.set_timeout(CONNECT_TIMEOUT).unwrap();
+        (Port::new(host), self.run(device))
+    }
+
+    pub fn connect_encoder(self) -> (Encoder<MemoryPipe>, SimulatorHandle) {
+        let counter_type = self.counter_type;
+        let resolution = self.resolution;
+        let (port, sim) = self.connect();
+        (
+            Encoder::with_resolution(port, counter_type, resolution),
+            sim,
+        )
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn run_async<T>(self, mut io: T) -> SimulatorHandle
+    where
+        T: ::tokio::io::AsyncRead + ::tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
+        use ::tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        self.spawn(move |encoder, stop| {
+            let runtime = ::tokio::runtime::Builder::new_current_thread()
+                .enable_time()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let mut buf = [0; 1];
+                while !stop.load(Ordering::Relaxed) {
+                    let timeout = encoder
+                        .lock()
+                        .unwrap()
+                        .time_until_next_response(Instant::now());
+
+                    let output =
+                        match ::tokio::time::timeout(timeout, io.read_exact(&mut buf)).await {
+                            Ok(Ok(_)) => encoder.lock().unwrap().handle_byte(buf[0]),
+                            Ok(Err(_)) => return,
+                            Err(_) => Vec::new(),
+                        };
+                    let frame = encoder
+                        .lock()
+                        .unwrap()
+                        .poll_continuous_response(Instant::now());
+
+                    for data in [Some(output), frame].into_iter().flatten() {
+                        match ::tokio::time::timeout(POLL_INTERVAL, io.write_all(&data)).await {
+                            Ok(Ok(())) | Err(_) => {}
+                            Ok(Err(_)) => return,
+                        }
+                    }
+                }
+            })
+        })
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn connect_async(
+        self,
+    ) -> (
+        super::tokio::Port<::tokio::io::DuplexStream>,
+        SimulatorHandle,
+    ) {
+        let (host, device) = ::tokio::io::duplex(DUPLEX_BUFFER_SIZE);
+        (
+            super::tokio::Port::new(host, DEFAULT_TIMEOUT),
+            self.run_async(device),
+        )
+    }
+}
+
+pub struct SimulatorHandle {
+    encoder: Arc<Mutex<SimulatedEncoder>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SimulatorHandle {
+    pub fn lock(&self) -> MutexGuard<'_, SimulatedEncoder> {
+        self.encoder.lock().unwrap()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+}
+
+impl Drop for SimulatorHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+    use crate::async_serial::{
+        Position, PositionAndDetailedStatus, PositionAndStatus, PositionAndTemperature,
+        PrefixedResponse, SelfCalibrationStatus, SerialNumber, ShortPosition,
+    };
+
+    #[test]
+    fn test_commands() {
+        let mut encoder = SimulatedEncoder::new(CounterType::MultiTurn);
+        encoder.set_angle(2.0 * PI + FRAC_PI_2);
+        encoder.set_serial_number("ABC123").unwrap();
+        assert!(matches!(
+            encoder.set_serial_number("ABC1234"),
+            Err(Error::SimulatorInvalidSerialNumber(_))
+        ));
+        encoder.set_temperature(-12.3);
+        encoder.set_detailed_status(0b00100000);
+        encoder.set_self_calibration_status(0b00000110);
+        encoder.set_warning(true);
+        let (mut port, _sim) = encoder.connect();
+
+        port.send_command(&Command::PositionRequest).unwrap();
+        let mut pos = Position::new(CounterType::MultiTurn);
+        port.receive(&mut pos).unwrap();
+        assert!(pos.is_valid_prefix());
+        assert_eq!(pos.multiturn_count(), Some(1));
+        assert_eq!(pos.position(), 4096);
+        assert!(!pos.is_error());
+        assert!(pos.is_warning());
+
+        port.send_command(&Command::ShortPositionRequest).unwrap();
+        let mut pos = ShortPosition::new(CounterType::MultiTurn);
+        port.receive(&mut pos).unwrap();
+        assert_approx_eq!(pos.angle_rad(), 2.0 * PI + FRAC_PI_2);
+
+        port.send_command(&Command::PositionRequestAndDetailedStatus)
+            .unwrap();
+        let mut status = PositionAndDetailedStatus::new(CounterType::MultiTurn);
+        port.receive(&mut status).unwrap();
+        assert!(status.is_valid_prefix());
+        assert!(status.is_temperature_out_of_range());
+        assert!(!status.is_signal_too_low());
+
+        port.send_command(&Command::PositionRequestAndTemperature)
+            .unwrap();
+        let mut temperature = PositionAndTemperature::new(CounterType::MultiTurn);
+        port.receive(&mut temperature).unwrap();
+        assert!(temperature.is_valid_prefix());
+        assert_approx_eq!(temperature.temperature(), -12.3);
+
+        port.send_command(&Command::SerialNumber).unwrap();
+        let mut serial_number = SerialNumber::new();
+        port.receive(&mut serial_number).unwrap();
+        assert!(serial_number.is_valid_prefix());
+        assert_eq!(serial_number.as_str().unwrap(), "ABC123");
+
+        port.send_command(&Command::SelfCalibrationStatusRequest)
+            .unwrap();
+        let mut calibration = SelfCalibrationStatus::new();
+        port.receive(&mut calibration).unwrap();
+        assert!(calibration.is_valid_prefix());
+        assert!(calibration.is_timeout());
+        assert_eq!(calibration.counter(), 2);
+    }
+
+    #[test]
+    fn test_programming() {
+        let mut encoder = SimulatedEncoder::new(CounterType::MultiTurn);
+        encoder.set_angle(-FRAC_PI_2);
+        let (mut port, sim) = encoder.connect();
+
+        port.send_programming_command(&ProgrammingCommand::PositionOffsetSetting(-4096))
+            .unwrap();
+        port.send_programming_command(&ProgrammingCommand::MultiturnCounterSetting(-3))
+            .unwrap();
+        port.send_programming_command(&ProgrammingCommand::BaudRateSetting(1_000_000))
+            .unwrap();
+
+        port.send_command(&Command::PositionRequest).unwrap();
+        let mut pos = Position::new(CounterType::MultiTurn);
+        port.receive(&mut pos).unwrap();
+        assert_eq!(pos.position(), 0);
+        assert_eq!(pos.multiturn_count(), Some(-3));
+        assert_eq!(sim.lock().position_offset(), -4096);
+        assert_eq!(sim.lock().baud_rate(), 1_000_000);
+
+        sim.lock().power_cycle();
+        assert_eq!(sim.lock().position_offset(), 0);
+
+        port.send_programming_command(&ProgrammingCommand::PositionOffsetSetting(100))
+            .unwrap();
+        port.send_programming_command(&ProgrammingCommand::ConfigurationParametersSave)
+            .unwrap();
+        sim.lock().power_cycle();
+        assert_eq!(sim.lock().position_offset(), 100);
+
+        port.send_programming_command(&ProgrammingCommand::ConfigurationParametersReset)
+            .unwrap();
+        assert_eq!(sim.lock().position_offset(), 0);
+    }
+
+    #[test]
+    fn test_continuous_response() {
+        let mut encoder = SimulatedEncoder::new(CounterType::SingleTurn);
+        encoder.set_angle(FRAC_PI_2);
+        let (mut port, sim) = encoder.connect();
+
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseSetting {
+            auto_start: false,
+            command: Command::PositionRequest,
+            period_micros: 1_000,
+        })
+        .unwrap();
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStart)
+            .unwrap();
+        assert!(sim.lock().is_continuous_response_running());
+
+        for _ in 0..5 {
+            let mut pos = Position::new(CounterType::SingleTurn);
+            port.receive(&mut pos).unwrap();
+            assert!(pos.is_valid_prefix());
+            assert_eq!(pos.position(), 4096);
+        }
+
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+            .unwrap();
+        assert!(!sim.lock().is_continuous_response_running());
+    }
+}
@@ -0,0 +1,9 @@
+mod continuous_stream;
+mod encoder;
+mod port;
+mod transport;
+
+pub use continuous_stream::ContinuousStream;
+pub use encoder::Encoder;
+pub use port::Port;
+pub use transport::AsyncTransport;
@@ -0,0 +1,217 @@
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use ::tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use futures_core::Stream;
+use tokio_serial::SerialStream;
+
+use super::{AsyncTransport, Encoder, Port};
+use crate::{
+    async_serial::{PositionResponse, ProgrammingCommand, Sample},
+    error::{Error, Result},
+    CounterType,
+};
+
+const CHANNEL_CAPACITY: usize = 1024;
+
+pub struct ContinuousStream<R, T: AsyncTransport = SerialStream> {
+    counter_type: CounterType,
+    receiver: mpsc::Receiver<Result<Sample<R>>>,
+    latest: Arc<Mutex<Option<Sample<R>>>>,
+    stop: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<Result<Port<T>>>>,
+}
+
+impl<R, T> ContinuousStream<R, T>
+where
+    R: PositionResponse + Clone + Send + 'static,
+    T: AsyncTransport + 'static,
+{
+    pub async fn start(
+        mut port: Port<T>,
+        counter_type: CounterType,
+        period_micros: u16,
+    ) -> Result<Self> {
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+            .await?;
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseSetting {
+            auto_start: false,
+            command: R::request_command(),
+            period_micros,
+        })
+        .await?;
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStart)
+            .await?;
+
+        let (sender, receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let (stop, stopped) = oneshot::channel();
+        let latest = Arc::new(Mutex::new(None));
+
+        let task = ::tokio::spawn(read_loop(
+            port,
+            counter_type,
+            sender,
+            latest.clone(),
+            stopped,
+        ));
+
+        Ok(Self {
+            counter_type,
+            receiver,
+            latest,
+            stop: Some(stop),
+            task: Some(task),
+        })
+    }
+}
+
+impl<R, T: AsyncTransport> ContinuousStream<R, T> {
+    pub fn counter_type(&self) -> CounterType {
+        self.counter_type
+    }
+
+    pub async fn recv(&mut self) -> Result<Sample<R>> {
+        self.receiver
+            .recv()
+            .await
+            .unwrap_or(Err(Error::AsyncSerialStreamClosed))
+    }
+
+    pub fn latest(&self) -> Option<Sample<R>>
+    where
+        R: Clone,
+    {
+        self.latest.lock().unwrap().clone()
+    }
+
+    pub async fn stop(mut self) -> Result<Port<T>> {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        match self.task.take() {
+            Some(task) => task.await.map_err(|_| Error::AsyncSerialStreamClosed)?,
+            None => Err(Error::AsyncSerialStreamClosed),
+        }
+    }
+}
+
+impl<R, T: AsyncTransport> Stream for ContinuousStream<R, T> {
+    type Item = Result<Sample<R>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
+
+impl<T: AsyncTransport + 'static> Encoder<T> {
+    pub async fn into_continuous_stream<R>(
+        self,
+        period_micros: u16,
+    ) -> Result<ContinuousStream<R, T>>
+    where
+        R: PositionResponse + Clone + Send + 'static,
+    {
+        let counter_type = self.counter_type();
+        ContinuousStream::start(self.into_port(), counter_type, period_micros).await
+    }
+}
+
+async fn read_loop<R: PositionResponse + Clone, T: AsyncTransport>(
+    mut port: Port<T>,
+    counter_type: CounterType,
+    sender: mpsc::Sender<Result<Sample<R>>>,
+    latest: Arc<Mutex<Option<Sample<R>>>>,
+    mut stopped: oneshot::Receiver<()>,
+) -> Result<Port<T>> {
+    let prefix = R::request_command().response_prefix();
+    let resolution = port.resolution();
+
+    loop {
+        let mut response = R::with_resolution(counter_type, resolution);
+        let received = ::tokio::select! {
+            _ = &mut stopped => break,
+            received = async {
+                match prefix {
+                    Some(prefix) => port.receive_prefixed(prefix, response.as_mut()).await.map(|_| ()),
+                    None => port.receive(&mut response).await,
+                }
+            } => received,
+        };
+
+        match received {
+            Ok(()) => {
+                let sample = Sample {
+                    response,
+                    timestamp: Instant::now(),
+                };
+                *latest.lock().unwrap() = Some(sample.clone());
+                let _ = sender.try_send(Ok(sample));
+            }
+            Err(Error::AsyncSerialFailedToReceive(e))
+                if e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                let _ = sender.try_send(Err(Error::AsyncSerialFailedToReceive(e)));
+            }
+            Err(e @ Error::AsyncSerialFrameNotFound { .. }) => {
+                let _ = sender.try_send(Err(e));
+            }
+            Err(e) => {
+                let _ = sender.try_send(Err(e));
+                break;
+            }
+        }
+    }
+
+    port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+        .await?;
+    port.clear_buffers().await?;
+    Ok(port)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64::consts::PI, future::poll_fn};
+
+    use super::*;
+    use crate::async_serial::{Position, PositionAndStatus, SimulatedEncoder};
+
+    #[::tokio::test]
+    async fn test_stream() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_angle_profile(|t| PI * t.as_secs_f64());
+        let (port, sim) = sim.connect_async();
+        let encoder = Encoder::new(port, CounterType::SingleTurn);
+
+        let mut stream = encoder
+            .into_continuous_stream::<Position>(1_000)
+            .await
+            .unwrap();
+        assert!(sim.lock().is_continuous_response_running());
+
+        let mut previous = stream.recv().await.unwrap();
+        for _ in 0..10 {
+            let sample = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx))
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(previous.timestamp <= sample.timestamp);
+            assert!(previous.response.position() <= sample.response.position());
+            previous = sample;
+        }
+        assert!(stream.latest().is_some());
+
+        let mut port = stream.stop().await.unwrap();
+        assert!(!sim.lock().is_continuous_response_running());
+
+        let mut serial_number = crate::async_serial::SerialNumber::new();
+        port.request(&mut serial_number).await.unwrap();
+        assert_eq!(serial_number.as_str().unwrap(), "SIM001");
+    }
+}
@@ -0,0 +1,211 @@
+use std::{path::Path, time::Duration};
+
+use tokio_serial::SerialStream;
+
+use super::{AsyncTransport, Port};
+use crate::{
+    async_serial::{
+        Command, Position, PositionAndDetailedStatus, PositionAndTemperature, PrefixedResponse,
+        ProgrammingCommand, SelfCalibrationStatus, SerialNumber, ShortPosition,
+    },
+    error::Result,
+    CounterType, Resolution,
+};
+
+pub struct Encoder<T: AsyncTransport = SerialStream> {
+    port: Port<T>,
+    counter_type: CounterType,
+}
+
+impl Encoder {
+    pub fn try_new(
+        path: impl AsRef<Path>,
+        baud_rate: u32,
+        timeout: Duration,
+        counter_type: CounterType,
+    ) -> Result<Self> {
+        Ok(Self::new(
+            Port::try_new(path, baud_rate, timeout)?,
+            counter_type,
+        ))
+    }
+}
+
+impl<T: AsyncTransport> Encoder<T> {
+    pub async fn detect(mut port: Port<T>) -> Result<Self> {
+        let counter_type = port.detect_counter_type().await?;
+        Ok(Self::new(port, counter_type))
+    }
+
+    pub async fn detect_and_stop(mut port: Port<T>) -> Result<Self> {
+        port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+            .await?;
+        port.clear_buffers().await?;
+        Self::detect(port).await
+    }
+
+    pub fn new(port: Port<T>, counter_type: CounterType) -> Self {
+        Self::with_resolution(port, counter_type, Resolution::default())
+    }
+
+    pub fn with_resolution(
+        mut port: Port<T>,
+        counter_type: CounterType,
+        resolution: Resolution,
+    ) -> Self {
+        port.set_resolution(resolution);
+        Self { port, counter_type }
+    }
+
+    pub fn counter_type(&self) -> CounterType {
+        self.counter_type
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.port.resolution()
+    }
+
+    pub fn port(&self) -> &Port<T> {
+        &self.port
+    }
+
+    pub fn port_mut(&mut self) -> &mut Port<T> {
+        &mut self.port
+    }
+
+    pub fn into_port(self) -> Port<T> {
+        self.port
+    }
+
+    async fn request<R: PrefixedResponse + AsMut<[u8]>>(&mut self, mut response: R) -> Result<R> {
+        self.port.request(&mut response).await?;
+        Ok(response)
+    }
+
+    pub async fn read_position(&mut self) -> Result<Position> {
+        self.request(Position::with_resolution(
+            self.counter_type,
+            self.resolution(),
+        ))
+        .await
+    }
+
+    pub async fn read_short_position(&mut self) -> Result<ShortPosition> {
+        let mut response = ShortPosition::with_resolution(self.counter_type, self.resolution());
+        self.port.clear_buffers().await?;
+        self.port
+            .send_command(&Command::ShortPositionRequest)
+            .await?;
+        self.port.receive(&mut response).await?;
+        Ok(response)
+    }
+
+    pub async fn read_detailed_status(&mut self) -> Result<PositionAndDetailedStatus> {
+        self.request(PositionAndDetailedStatus::with_resolution(
+            self.counter_type,
+            self.resolution(),
+        ))
+        .await
+    }
+
+    pub async fn read_temperature(&mut self) -> Result<PositionAndTemperature> {
+        self.request(PositionAndTemperature::with_resolution(
+            self.counter_type,
+            self.resolution(),
+        ))
+        .await
+    }
+
+    pub async fn read_serial_number(&mut self) -> Result<SerialNumber> {
+        let serial_number = self.request(SerialNumber::new()).await?;
+        serial_number.as_str()?;
+        Ok(serial_number)
+    }
+
+    pub async fn read_self_calibration_status(&mut self) -> Result<SelfCalibrationStatus> {
+        self.request(SelfCalibrationStatus::new()).await
+    }
+
+    pub async fn send_programming_command(&mut self, command: &ProgrammingCommand) -> Result<()> {
+        self.port.send_programming_command(command).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::FRAC_PI_2;
+
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+    use crate::async_serial::{PositionAndStatus, SimulatedEncoder};
+
+    #[::tokio::test]
+    async fn test_read() {
+        let mut sim = SimulatedEncoder::new(CounterType::MultiTurn);
+        sim.set_angle(FRAC_PI_2);
+        sim.set_serial_number("TOKIO1").unwrap();
+        sim.set_temperature(-5.5);
+        let (port, sim) = sim.connect_async();
+        let mut encoder = Encoder::new(port, CounterType::MultiTurn);
+
+        sim.lock().queue_noise(&[0x00, 0x12]);
+        let pos = encoder.read_position().await.unwrap();
+        assert_eq!(pos.position(), 4096);
+        assert_eq!(encoder.port().skipped_bytes(), 2);
+
+        let pos = encoder.read_short_position().await.unwrap();
+        assert_approx_eq!(pos.angle_rad(), FRAC_PI_2);
+
+        let status = encoder.read_detailed_status().await.unwrap();
+        assert!(!status.is_speed_too_high());
+
+        let temperature = encoder.read_temperature().await.unwrap();
+        assert_approx_eq!(temperature.temperature(), -5.5);
+
+        let serial_number = encoder.read_serial_number().await.unwrap();
+        assert_eq!(serial_number.as_str().unwrap(), "TOKIO1");
+
+        let calibration = encoder.read_self_calibration_status().await.unwrap();
+        assert!(!calibration.is_timeout());
+
+        encoder
+            .send_programming_command(&ProgrammingCommand::PositionOffsetSetting(4096))
+            .await
+            .unwrap();
+        assert_eq!(encoder.read_position().await.unwrap().position(), 0);
+    }
+
+    #[::tokio::test]
+    async fn test_detect() {
+        for counter_type in [CounterType::SingleTurn, CounterType::MultiTurn] {
+            let (port, _sim) = SimulatedEncoder::new(counter_type).connect_async();
+            let encoder = Encoder::detect(port).await.unwrap();
+            assert_eq!(encoder.counter_type(), counter_type);
+        }
+
+        let mut sim = SimulatedEncoder::new(CounterType::MultiTurn);
+        for byte in (ProgrammingCommand::ContinuousResponseSetting {
+            auto_start: true,
+            command: Command::PositionRequest,
+            period_micros: 5_000,
+        })
+        .to_bytes(Resolution::default())
+        .into_iter()
+        .chain(ProgrammingCommand::ConfigurationParametersSave.to_bytes(Resolution::default()))
+        {
+            sim.handle_byte(byte);
+        }
+        sim.power_cycle();
+        let (mut port, sim) = sim.connect_async();
+
+        assert_eq!(
+            port.detect_counter_type().await.unwrap(),
+            CounterType::MultiTurn
+        );
+        assert!(sim.lock().is_continuous_response_running());
+        let encoder = Encoder::detect_and_stop(port).await.unwrap();
+        assert_eq!(encoder.counter_type(), CounterType::MultiTurn);
+        assert!(!sim.lock().is_continuous_response_running());
+    }
+}
@@ -0,0 +1,349 @@
+use std::{io, path::Path, time::Duration};
+
+use ::tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpStream, ToSocketAddrs},
+};
+use tokio_serial::{DataBits, Parity, SerialPortBuilderExt, SerialStream, StopBits};
+
+use super::AsyncTransport;
+use crate::{
+    async_serial::{
+        port::{
+            counter_type_from_length, position_frame_lengths, streaming_counter_type,
+            PrefixScanner, ResponseScanner, COUNTER_TYPE_LISTEN_WINDOW, INTER_BYTE_TIMEOUT,
+            PROGRAMMING_DELAY_BETWEEN_BYTES,
+        },
+        Command, PrefixedResponse, ProgrammingCommand, Response,
+    },
+    error::{Error, Result},
+    CounterType, Resolution,
+};
+
+const READ_CHUNK_SIZE: usize = 256;
+
+pub struct Port<T: AsyncTransport = SerialStream> {
+    inner: T,
+    timeout: Duration,
+    skipped_bytes: u64,
+    resolution: Resolution,
+    read_buf: Vec<u8>,
+}
+
+impl Port {
+    pub fn try_new(path: impl AsRef<Path>, baud_rate: u32, timeout: Duration) -> Result<Self> {
+        let inner = tokio_serial::new(path.as_ref().to_string_lossy(), baud_rate)
+            .data_bits(DataBits::Eight)
+            .stop_bits(StopBits::One)
+            .parity(Parity::None)
+            .timeout(timeout)
+            .open_native_async()
+            .map_err(|source| Error::AsyncSerialFailedToOpen {
+                source,
+                path: path.as_ref().into(),
+            })?;
+
+        Ok(Self::new(inner, timeout))
+    }
+}
+
+impl Port<TcpStream> {
+    pub async fn try_connect(addr: impl ToSocketAddrs, timeout: Duration) -> Result<Self> {
+        let inner = TcpStream::connect(addr)
+            .await
+            .map_err(Error::AsyncSerialFailedToConnect)?;
+        inner
+            .set_nodelay(true)
+            .map_err(Error::AsyncSerialFailedToConnect)?;
+
+        Ok(Self::new(inner, timeout))
+    }
+}
+
+impl<T: AsyncTransport> Port<T> {
+    pub fn new(transport: T, timeout: Duration) -> Self {
+        Self {
+            inner: transport,
+            timeout,
+            skipped_bytes: 0,
+            resolution: Resolution::default(),
+            read_buf: Vec::new(),
+        }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    pub fn into_transport(self) -> T {
+        self.inner
+    }
+
+    pub fn skipped_bytes(&self) -> u64 {
+        self.skipped_bytes
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub async fn clear_buffers(&mut self) -> Result<()> {
+        self.read_buf.clear();
+        self.inner
+            .clear_input()
+            .map_err(Error::AsyncSerialFailedToConfigure)?;
+
+        let mut buf = [0; 256];
+        loop {
+            match ::tokio::time::timeout(Duration::ZERO, self.inner.read(&mut buf)).await {
+                Ok(Ok(0)) | Err(_) => return Ok(()),
+                Ok(Ok(_)) => continue,
+                Ok(Err(e)) => return Err(Error::AsyncSerialFailedToConfigure(e)),
+            }
+        }
+    }
+
+    async fn fill_read_buf(&mut self, len: usize, timeout: Duration) -> io::Result<()> {
+        let deadline = ::tokio::time::Instant::now() + timeout;
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        while self.read_buf.len() < len {
+            match ::tokio::time::timeout_at(deadline, self.inner.read(&mut chunk)).await {
+                Ok(Ok(0)) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(Ok(n)) => self.read_buf.extend_from_slice(&chunk[..n]),
+                Ok(Err(e)) => return Err(e),
+                Err(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "Operation timed out",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn read_exact_within(&mut self, buf: &mut [u8], timeout: Duration) -> io::Result<()> {
+        self.fill_read_buf(buf.len(), timeout).await?;
+        buf.copy_from_slice(&self.read_buf[..buf.len()]);
+        self.read_buf.drain(..buf.len());
+        Ok(())
+    }
+
+    async fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.read_exact_within(buf, self.timeout).await
+    }
+
+    async fn peek_byte(&mut self) -> io::Result<u8> {
+        self.fill_read_buf(1, self.timeout).await?;
+        Ok(self.read_buf[0])
+    }
+
+    async fn send_byte(&mut self, byte: u8) -> Result<()> {
+        let buf = [byte];
+        self.inner
+            .write_all(&buf)
+            .await
+            .map_err(Error::AsyncSerialFailedToSend)?;
+        Ok(())
+    }
+
+    async fn drop_until(&mut self, byte: u8) -> Result<()> {
+        let mut buf = [0; 1];
+        loop {
+            self.read_exact(&mut buf)
+                .await
+                .map_err(Error::AsyncSerialFailedToReceive)?;
+            if buf[0] == byte {
+                return Ok(());
+            }
+        }
+    }
+
+    pub async fn send_command(&mut self, command: &Command) -> Result<()> {
+        self.send_byte(command.to_byte()).await
+    }
+
+    pub async fn send_programming_command(&mut self, command: &ProgrammingCommand) -> Result<()> {
+        for byte in command.to_bytes(self.resolution) {
+            self.send_byte(byte).await?;
+            ::tokio::time::sleep(PROGRAMMING_DELAY_BETWEEN_BYTES).await;
+
+            self.drop_until(byte).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn receive(&mut self, buf: &mut impl AsMut<[u8]>) -> Result<()> {
+        self.read_exact(buf.as_mut())
+            .await
+            .map_err(Error::AsyncSerialFailedToReceive)
+    }
+
+    pub async fn receive_frame<R: PrefixedResponse + AsMut<[u8]>>(
+        &mut self,
+        response: &mut R,
+    ) -> Result<usize> {
+        self.receive_prefixed(R::command().to_byte(), response.as_mut())
+            .await
+    }
+
+    pub(crate) async fn receive_prefixed(&mut self, prefix: u8, buf: &mut [u8]) -> Result<usize> {
+        let mut scanner = PrefixScanner::new(prefix);
+        let synced = loop {
+            let byte = self.peek_byte().await;
+            match scanner.push(byte) {
+                Some(synced) => break synced,
+                None => {
+                    self.read_buf.remove(0);
+                }
+            }
+        };
+        self.skipped_bytes += scanner.skipped() as u64;
+        synced?;
+
+        self.read_exact(buf)
+            .await
+            .map_err(Error::AsyncSerialFailedToReceive)?;
+        Ok(scanner.skipped())
+    }
+
+    pub async fn receive_response(&mut self, counter_type: CounterType) -> Result<Response> {
+        let mut scanner = ResponseScanner::new(counter_type, self.resolution);
+        let response = loop {
+            let byte = self.peek_byte().await;
+            match scanner.push(byte) {
+                Some(response) => break response,
+                None => {
+                    self.read_buf.remove(0);
+                }
+            }
+        };
+        self.skipped_bytes += scanner.skipped() as u64;
+        let mut response = response?;
+
+        self.read_exact(response.as_mut())
+            .await
+            .map_err(Error::AsyncSerialFailedToReceive)?;
+        if let Response::SerialNumber(serial_number) = &response {
+            serial_number.as_str()?;
+        }
+        Ok(response)
+    }
+
+    pub async fn detect_counter_type(&mut self) -> Result<CounterType> {
+        if let Some(counter_type) = self
+            .detect_streaming_counter_type(COUNTER_TYPE_LISTEN_WINDOW)
+            .await?
+        {
+            return Ok(counter_type);
+        }
+
+        let (single_turn, multi_turn) = position_frame_lengths();
+        let mut frame = vec![0; single_turn.max(multi_turn) + 1];
+
+        self.clear_buffers().await?;
+        self.send_command(&Command::PositionRequest).await?;
+        self.receive_prefixed(Command::PositionRequest.to_byte(), &mut frame[..1])
+            .await?;
+        let mut length = 1;
+        while length < frame.len()
+            && self
+                .read_exact_within(&mut frame[length..][..1], INTER_BYTE_TIMEOUT)
+                .await
+                .is_ok()
+        {
+            length += 1;
+        }
+
+        counter_type_from_length(length)
+    }
+
+    pub async fn detect_streaming_counter_type(
+        &mut self,
+        window: Duration,
+    ) -> Result<Option<CounterType>> {
+        self.clear_buffers().await?;
+        let deadline = ::tokio::time::Instant::now() + window;
+        let mut chunk = [0; READ_CHUNK_SIZE];
+        loop {
+            match ::tokio::time::timeout_at(deadline, self.inner.read(&mut chunk)).await {
+                Ok(Ok(0)) | Err(_) => break,
+                Ok(Ok(n)) => self.read_buf.extend_from_slice(&chunk[..n]),
+                Ok(Err(e)) => return Err(Error::AsyncSerialFailedToReceive(e)),
+            }
+        }
+
+        let captured = std::mem::take(&mut self.read_buf);
+        Ok(streaming_counter_type(&captured, self.resolution))
+    }
+
+    pub async fn request<R: PrefixedResponse + AsMut<[u8]>>(
+        &mut self,
+        response: &mut R,
+    ) -> Result<usize> {
+        self.clear_buffers().await?;
+        self.send_command(&R::command()).await?;
+        self.receive_frame(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::tokio::io::duplex;
+
+    use super::*;
+    use crate::async_serial::{Position, PositionAndStatus};
+
+    #[::tokio::test]
+    async fn test_receive_after_timeout() {
+        let (host, mut device) = duplex(64);
+        let mut port = Port::new(host, Duration::from_millis(50));
+
+        device.write_all(b"1\x40").await.unwrap();
+        let mut position = Position::new(CounterType::SingleTurn);
+        assert!(matches!(
+            port.receive_frame(&mut position).await,
+            Err(Error::AsyncSerialFailedToReceive(e)) if e.kind() == io::ErrorKind::TimedOut
+        ));
+
+        device.write_all(b"\x03").await.unwrap();
+        port.receive_frame(&mut position).await.unwrap();
+        assert_eq!(position.position(), 4096);
+    }
+
+    #[::tokio::test]
+    async fn test_receive_response() {
+        let (host, mut device) = duplex(64);
+        let mut port = Port::new(host, Duration::from_millis(50));
+
+        device.write_all(b"\x00\xFFv1234561\x40\x03").await.unwrap();
+
+        let response = port
+            .receive_response(CounterType::SingleTurn)
+            .await
+            .unwrap();
+        assert!(matches!(response, Response::SerialNumber(s) if s.to_string() == "123456"));
+        let response = port
+            .receive_response(CounterType::SingleTurn)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.position_and_status().map(|p| p.position()),
+            Some(4096)
+        );
+        assert_eq!(port.skipped_bytes(), 2);
+    }
+}
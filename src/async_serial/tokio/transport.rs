@@ -0,0 +1,23 @@
+use std::io;
+
+use ::tokio::{
+    io::{AsyncRead, AsyncWrite, DuplexStream},
+    net::TcpStream,
+};
+use tokio_serial::{ClearBuffer, SerialPort, SerialStream};
+
+pub trait AsyncTransport: AsyncRead + AsyncWrite + Unpin + Send {
+    fn clear_input(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncTransport for SerialStream {
+    fn clear_input(&mut self) -> io::Result<()> {
+        self.clear(ClearBuffer::All).map_err(io::Error::from)
+    }
+}
+
+impl AsyncTransport for TcpStream {}
+
+impl AsyncTransport for DuplexStream {}
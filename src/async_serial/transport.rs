@@ -0,0 +1,17 @@
+use std::{io, time::Duration};
+
+mod memory;
+mod serial;
+mod tcp;
+
+pub use memory::MemoryPipe;
+
+pub trait Transport {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()>;
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()>;
+    fn timeout(&self) -> Duration;
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()>;
+    fn baud_rate(&self) -> Option<u32>;
+    fn set_baud_rate(&mut self, baud_rate: u32) -> io::Result<()>;
+    fn clear_buffers(&mut self) -> io::Result<()>;
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Condvar, Mutex},
+    time::{Duration, Instant},
+};
+
+use super::Transport;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+const GARBLED_BYTE: u8 = 0xFF;
+
+#[derive(Default)]
+struct ChannelState {
+    buf: VecDeque<(u8, Option<u32>)>,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Channel {
+    state: Mutex<ChannelState>,
+    ready: Condvar,
+}
+
+impl Channel {
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.ready.notify_all();
+    }
+}
+
+pub struct MemoryPipe {
+    rx: Arc<Channel>,
+    tx: Arc<Channel>,
+    timeout: Duration,
+    baud_rate: Option<u32>,
+}
+
+impl MemoryPipe {
+    pub fn pair() -> (Self, Self) {
+        let a = Arc::new(Channel::default());
+        let b = Arc::new(Channel::default());
+        (
+            Self {
+                rx: a.clone(),
+                tx: b.clone(),
+                timeout: DEFAULT_TIMEOUT,
+                baud_rate: None,
+            },
+            Self {
+                rx: b,
+                tx: a,
+                timeout: DEFAULT_TIMEOUT,
+                baud_rate: None,
+            },
+        )
+    }
+
+    pub fn bytes_to_read(&self) -> usize {
+        self.rx.state.lock().unwrap().buf.len()
+    }
+}
+
+impl Transport for MemoryPipe {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        let deadline = Instant::now() + self.timeout;
+        let mut state = self.rx.state.lock().unwrap();
+        for byte in buf.iter_mut() {
+            loop {
+                if let Some((b, baud_rate)) = state.buf.pop_front() {
+                    *byte = match (baud_rate, self.baud_rate) {
+                        (Some(sent), Some(received)) if sent != received => GARBLED_BYTE,
+                        _ => b,
+                    };
+                    break;
+                }
+                if state.closed {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "Operation timed out",
+                    ));
+                }
+                state = self.rx.ready.wait_timeout(state, deadline - now).unwrap().0;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        let mut state = self.tx.state.lock().unwrap();
+        if state.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+        state.buf.extend(buf.iter().map(|&b| (b, self.baud_rate)));
+        self.tx.ready.notify_all();
+        Ok(())
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    fn baud_rate(&self) -> Option<u32> {
+        self.baud_rate
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> io::Result<()> {
+        self.baud_rate = Some(baud_rate);
+        Ok(())
+    }
+
+    fn clear_buffers(&mut self) -> io::Result<()> {
+        self.rx.state.lock().unwrap().buf.clear();
+        Ok(())
+    }
+}
+
+impl Drop for MemoryPipe {
+    fn drop(&mut self) {
+        self.rx.close();
+        self.tx.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let (mut a, mut b) = MemoryPipe::pair();
+
+        a.write_all(&[1, 2, 3]).unwrap();
+        b.write_all(&[4]).unwrap();
+        assert_eq!(b.bytes_to_read(), 3);
+
+        let mut buf = [0; 3];
+        b.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+
+        let mut buf = [0; 1];
+        a.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [4]);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let (mut a, mut b) = MemoryPipe::pair();
+        a.set_timeout(Duration::from_millis(10)).unwrap();
+
+        b.write_all(&[1]).unwrap();
+        let mut buf = [0; 2];
+        let err = a.read_exact(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert_eq!(a.bytes_to_read(), 0);
+    }
+
+    #[test]
+    fn test_clear_buffers() {
+        let (mut a, mut b) = MemoryPipe::pair();
+
+        b.write_all(&[1, 2, 3]).unwrap();
+        a.clear_buffers().unwrap();
+        assert_eq!(a.bytes_to_read(), 0);
+    }
+
+    #[test]
+    fn test_baud_rate_mismatch() {
+        let (mut a, mut b) = MemoryPipe::pair();
+        b.set_baud_rate(115_200).unwrap();
+
+        a.write_all(&[1]).unwrap();
+        a.set_baud_rate(9_600).unwrap();
+        a.write_all(&[2]).unwrap();
+        a.set_baud_rate(115_200).unwrap();
+        a.write_all(&[3]).unwrap();
+
+        let mut buf = [0; 3];
+        b.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, GARBLED_BYTE, 3]);
+    }
+
+    #[test]
+    fn test_closed() {
+        let (mut a, b) = MemoryPipe::pair();
+        drop(b);
+
+        let mut buf = [0; 1];
+        assert_eq!(
+            a.read_exact(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(
+            a.write_all(&buf).unwrap_err().kind(),
+            io::ErrorKind::BrokenPipe
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use std::{io, time::Duration};
+
+use serialport::{ClearBuffer, SerialPort};
+
+use super::Transport;
+
+impl Transport for Box<dyn SerialPort> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        io::Read::read_exact(self, buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        io::Write::write_all(self, buf)
+    }
+
+    fn timeout(&self) -> Duration {
+        SerialPort::timeout(self.as_ref())
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        SerialPort::set_timeout(self.as_mut(), timeout).map_err(io::Error::from)
+    }
+
+    fn baud_rate(&self) -> Option<u32> {
+        SerialPort::baud_rate(self.as_ref()).ok()
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> io::Result<()> {
+        SerialPort::set_baud_rate(self.as_mut(), baud_rate).map_err(io::Error::from)
+    }
+
+    fn clear_buffers(&mut self) -> io::Result<()> {
+        self.clear(ClearBuffer::All).map_err(io::Error::from)
+    }
+}
+
+#[cfg(unix)]
+impl Transport for serialport::TTYPort {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        io::Read::read_exact(self, buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        io::Write::write_all(self, buf)
+    }
+
+    fn timeout(&self) -> Duration {
+        SerialPort::timeout(self)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        SerialPort::set_timeout(self, timeout).map_err(io::Error::from)
+    }
+
+    fn baud_rate(&self) -> Option<u32> {
+        SerialPort::baud_rate(self).ok()
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> io::Result<()> {
+        SerialPort::set_baud_rate(self, baud_rate).map_err(io::Error::from)
+    }
+
+    fn clear_buffers(&mut self) -> io::Result<()> {
+        self.clear(ClearBuffer::All).map_err(io::Error::from)
+    }
+}
@@ -0,0 +1,51 @@
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use super::Transport;
+
+impl Transport for TcpStream {
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        Read::read_exact(self, buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        Write::write_all(self, buf)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.read_timeout().ok().flatten().unwrap_or(Duration::MAX)
+    }
+
+    fn set_timeout(&mut self, timeout: Duration) -> io::Result<()> {
+        let timeout = (timeout != Duration::MAX).then_some(timeout);
+        self.set_read_timeout(timeout)?;
+        self.set_write_timeout(timeout)
+    }
+
+    fn baud_rate(&self) -> Option<u32> {
+        None
+    }
+
+    fn set_baud_rate(&mut self, _baud_rate: u32) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn clear_buffers(&mut self) -> io::Result<()> {
+        self.set_nonblocking(true)?;
+        let mut buf = [0; 256];
+        let result = loop {
+            match self.read(&mut buf) {
+                Ok(0) => break Ok(()),
+                Ok(_) => continue,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => break Err(e),
+            }
+        };
+        self.set_nonblocking(false)?;
+        result
+    }
+}
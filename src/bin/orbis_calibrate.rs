@@ -0,0 +1,74 @@
+use std::{
+    io::{self, BufRead, Write},
+    time::Duration,
+};
+
+use orbis_encoder::{async_serial::*, error::Error};
+
+const DEFAULT_DEVICE_FILE_PATH: &str = "/dev/ttyUSB0";
+const BAUD_RATE: u32 = 1_000_000;
+const TIMEOUT: Duration = Duration::from_millis(1000);
+const CALIBRATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut opts = getopts::Options::new();
+    opts.optopt("p", "port", "serial port path", "PATH");
+    opts.optopt(
+        "b",
+        "baud",
+        "baud rate or \"auto\" (default 1000000)",
+        "BAUD",
+    );
+    opts.optflag("y", "yes", "start without waiting for confirmation");
+    let matches = opts.parse(&args[1..]).unwrap();
+    let path = matches
+        .opt_str("p")
+        .unwrap_or_else(|| DEFAULT_DEVICE_FILE_PATH.to_owned());
+    let baud_rate = matches
+        .opt_get_default("b", BaudRate::Fixed(BAUD_RATE))
+        .expect("invalid baud rate");
+
+    let mut port = Port::try_open(path, baud_rate, TIMEOUT).unwrap();
+    port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+        .unwrap();
+    let mut encoder = Encoder::detect(port).unwrap();
+    println!(
+        "encoder {} ({:?})",
+        encoder.read_serial_number().unwrap(),
+        encoder.counter_type()
+    );
+
+    if !matches.opt_present("y") {
+        print!("mount the magnet, then press Enter and rotate the shaft slowly by hand ");
+        io::stdout().flush().unwrap();
+        io::stdin().lock().lines().next();
+    }
+
+    println!("calibrating, keep rotating the shaft...");
+    let result = encoder.calibrate(CALIBRATION_TIMEOUT, |progress| {
+        print!(
+            "\r{:5.1} s  {:+7.2} rev",
+            progress.elapsed.as_secs_f64(),
+            progress.revolutions
+        );
+        io::stdout().flush().unwrap();
+    });
+    println!();
+
+    match result {
+        Ok(()) => println!("calibration succeeded, parameters saved"),
+        Err(Error::AsyncSerialSelfCalibrationOutOfRange) => {
+            eprintln!("calibration failed: magnet signal out of range, check the air gap");
+            std::process::exit(1);
+        }
+        Err(Error::AsyncSerialSelfCalibrationTimeout) => {
+            eprintln!("calibration failed: the shaft was not rotated a full turn in time");
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("calibration failed: {e}");
+            std::process::exit(1);
+        }
+    }
+}
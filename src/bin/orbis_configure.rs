@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use orbis_encoder::{async_serial::*, EncoderConfig};
+
+const DEFAULT_DEVICE_FILE_PATH: &str = "/dev/ttyUSB0";
+const BAUD_RATE: u32 = 1_000_000;
+const TIMEOUT: Duration = Duration::from_millis(1000);
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut opts = getopts::Options::new();
+    opts.optopt("p", "port", "serial port path", "PATH");
+    opts.optopt(
+        "b",
+        "baud",
+        "baud rate or \"auto\" (default 1000000)",
+        "BAUD",
+    );
+    opts.optopt("c", "config", "config file (.toml or .json)", "PATH");
+    opts.optflag("n", "dry-run", "print the bytes without sending them");
+    let matches = opts.parse(&args[1..]).unwrap();
+    let config_path = matches.opt_str("c").expect("--config is required");
+    let config = EncoderConfig::load(config_path).unwrap();
+
+    if matches.opt_present("n") {
+        for (command, bytes) in config.dry_run().unwrap() {
+            let hex: Vec<_> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+            println!("{:<60} {}", format!("{command:?}"), hex.join(" "));
+        }
+        return;
+    }
+
+    let path = matches
+        .opt_str("p")
+        .unwrap_or_else(|| DEFAULT_DEVICE_FILE_PATH.to_owned());
+    let baud_rate = matches
+        .opt_get_default("b", BaudRate::Fixed(BAUD_RATE))
+        .expect("invalid baud rate");
+
+    let mut port = Port::try_open(path, baud_rate, TIMEOUT).unwrap();
+    port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
+        .unwrap();
+    let counter_type = port.detect_counter_type().unwrap();
+    let mut encoder = Encoder::with_resolution(port, counter_type, config.resolution().unwrap());
+    config.apply(&mut encoder).unwrap();
+
+    println!("applied {} command(s)", config.programming_commands().len());
+}
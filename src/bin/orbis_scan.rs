@@ -0,0 +1,52 @@
+use orbis_encoder::async_serial::*;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut opts = getopts::Options::new();
+    opts.optflag("j", "json", "print the result as JSON");
+    opts.optopt(
+        "b",
+        "baud",
+        "comma separated baud rates to probe (default: all supported)",
+        "BAUD[,BAUD...]",
+    );
+    let matches = opts.parse(&args[1..]).unwrap();
+    let baud_rates = match matches.opt_str("b") {
+        Some(list) => list
+            .split(',')
+            .map(|b| b.trim().parse().expect("invalid baud rate"))
+            .collect(),
+        None => SUPPORTED_BAUD_RATES.to_vec(),
+    };
+
+    let report = scan(&baud_rates).unwrap();
+    for skipped in &report.skipped {
+        eprintln!("skipped {}: {}", skipped.path, skipped.reason);
+    }
+
+    if matches.opt_present("j") {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report.encoders).unwrap()
+        );
+        return;
+    }
+
+    println!(
+        "{:<20} {:>8} {:<8} {:<9} {:<16} PRODUCT",
+        "PATH", "BAUD", "SERIAL", "VID:PID", "USB SERIAL"
+    );
+    for encoder in &report.encoders {
+        let usb = encoder.usb.clone().unwrap_or_default();
+        println!(
+            "{:<20} {:>8} {:<8} {:04x}:{:04x} {:<16} {}",
+            encoder.path,
+            encoder.baud_rate,
+            encoder.serial_number,
+            usb.vid,
+            usb.pid,
+            usb.serial_number.as_deref().unwrap_or("-"),
+            usb.product.as_deref().unwrap_or("-"),
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use orbis_encoder::{async_serial::*, CounterType, EncoderConfig};
+
+const DEFAULT_DEVICE_FILE_PATH: &str = "/dev/ttyUSB0";
+const BAUD_RATE: u32 = 1_000_000;
+const TIMEOUT: Duration = Duration::from_millis(100);
+const LISTEN_WINDOW: Duration = Duration::from_millis(500);
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut opts = getopts::Options::new();
+    opts.optopt("p", "port", "serial port path", "PATH");
+    opts.optopt(
+        "b",
+        "baud",
+        "baud rate or \"auto\" (default 1000000)",
+        "BAUD",
+    );
+    opts.optopt("c", "config", "expected config to compare against", "PATH");
+    let matches = opts.parse(&args[1..]).unwrap();
+    let path = matches
+        .opt_str("p")
+        .unwrap_or_else(|| DEFAULT_DEVICE_FILE_PATH.to_owned());
+    let baud_rate = matches
+        .opt_get_default("b", BaudRate::Fixed(BAUD_RATE))
+        .expect("invalid baud rate");
+
+    let mut port = Port::try_open(path, baud_rate, TIMEOUT).unwrap();
+    let counter_type = match port.detect_streaming_counter_type(LISTEN_WINDOW) {
+        Ok(Some(counter_type)) => counter_type,
+        _ => port.detect_counter_type().unwrap_or_else(|e| {
+            eprintln!("{e}, assuming single-turn");
+            CounterType::SingleTurn
+        }),
+    };
+    let mut encoder = Encoder::new(port, counter_type);
+    let snapshot = encoder.snapshot(LISTEN_WINDOW).unwrap();
+    println!("{}", snapshot.to_json().unwrap());
+
+    if let Some(config_path) = matches.opt_str("c") {
+        let config = EncoderConfig::load(config_path).unwrap();
+        for field in snapshot.unverified(&config) {
+            eprintln!("{field}: not verifiable from a snapshot");
+        }
+        let differences = snapshot.differences(&config);
+        for difference in &differences {
+            eprintln!(
+                "{}: expected {}, actual {}",
+                difference.field, difference.expected, difference.actual
+            );
+        }
+        if !differences.is_empty() {
+            std::process::exit(1);
+        }
+    }
+}
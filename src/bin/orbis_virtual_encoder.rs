@@ -0,0 +1,67 @@
+// buggy: https://github.com/rust-lang/rust-clippy/issues?q=is%3Aissue+derive_partial_eq_without_eq
+#![allow(clippy::derive_partial_eq_without_eq)]
+
+#[cfg(unix)]
+fn main() {
+    use std::{f64::consts::PI, time::Duration};
+
+    use orbis_encoder::{async_serial::*, CounterType, Resolution};
+    use serialport::{SerialPort, TTYPort};
+
+    const CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+    let args: Vec<String> = std::env::args().collect();
+    let mut opts = getopts::Options::new();
+    opts.optopt(
+        "c",
+        "counter_type",
+        "counter type (single or multi)",
+        "COUNTER_TYPE",
+    );
+    opts.optopt("", "bits", "resolution in bits (default 14)", "BITS");
+    opts.optopt("s", "serial", "serial number (6 characters)", "SERIAL");
+    opts.optopt("r", "rpm", "constant shaft speed (rpm)", "RPM");
+    opts.optopt("t", "temperature", "temperature (degree Celsius)", "TEMP");
+    opts.optopt("l", "link", "create a symlink to the slave device", "PATH");
+    let matches = opts.parse(&args[1..]).unwrap();
+    let counter_type = match matches.opt_str("c").as_deref() {
+        None | Some("single") => CounterType::SingleTurn,
+        Some("multi") => CounterType::MultiTurn,
+        Some(s) => panic!("unknown counter type: {s}"),
+    };
+
+    let mut encoder = SimulatedEncoder::new(counter_type);
+    if let Some(bits) = matches.opt_str("bits") {
+        encoder.set_resolution(Resolution::new(bits.parse().unwrap()).expect("invalid resolution"));
+    }
+    if let Some(serial_number) = matches.opt_str("s") {
+        encoder
+            .set_serial_number(&serial_number)
+            .expect("invalid serial number");
+    }
+    if let Some(rpm) = matches.opt_str("r") {
+        let rpm: f64 = rpm.parse().unwrap();
+        encoder.set_angle_profile(move |t| 2.0 * PI * rpm / 60.0 * t.as_secs_f64());
+    }
+    if let Some(temperature) = matches.opt_str("t") {
+        encoder.set_temperature(temperature.parse().unwrap());
+    }
+
+    let (master, slave) = TTYPort::pair().expect("failed to open pseudo-terminal");
+    let slave_path = slave.name().unwrap();
+    if let Some(link) = matches.opt_str("l") {
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&slave_path, &link).expect("failed to create symlink");
+    }
+    println!("{slave_path}");
+
+    let sim = encoder.run(master);
+    while sim.is_running() {
+        std::thread::sleep(CHECK_INTERVAL);
+    }
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("orbis_virtual_encoder requires a pseudo-terminal and is only supported on unix");
+}
@@ -13,12 +13,21 @@ fn main() {
     let args: Vec<String> = std::env::args().collect();
     let mut opts = getopts::Options::new();
     opts.optopt("p", "port", "serial port path", "PATH");
+    opts.optopt(
+        "b",
+        "baud",
+        "baud rate or \"auto\" (default 1000000)",
+        "BAUD",
+    );
     let matches = opts.parse(&args[1..]).unwrap();
     let path = matches
         .opt_str("p")
         .unwrap_or_else(|| DEFAULT_DEVICE_FILE_PATH.to_owned());
 
-    let mut port = Port::try_new(path, BAUD_RATE, TIMEOUT).unwrap();
+    let baud_rate = matches
+        .opt_get_default("b", BaudRate::Fixed(BAUD_RATE))
+        .expect("invalid baud rate");
+    let mut port = Port::try_open(path, baud_rate, TIMEOUT).unwrap();
 
     port.send_programming_command(&ProgrammingCommand::ContinuousResponseStop)
         .unwrap();
@@ -0,0 +1,432 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    async_serial::{Command, Encoder, ProgrammingCommand, Transport},
+    error::{Error, Result},
+    CounterType, Resolution,
+};
+
+const PERIOD_TOLERANCE: f64 = 0.25;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContinuousResponseSettings {
+    pub command: Command,
+    pub period_micros: u16,
+    #[serde(default)]
+    pub auto_start: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncoderConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution_bits: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position_offset: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiturn_preset: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baud_rate: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continuous_response: Option<ContinuousResponseSettings>,
+}
+
+impl EncoderConfig {
+    pub fn from_toml(s: &str) -> Result<Self> {
+        let config: Self = toml::from_str(s).map_err(|e| Error::ConfigInvalid(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_json(s: &str) -> Result<Self> {
+        let config: Self =
+            serde_json::from_str(s).map_err(|e| Error::ConfigInvalid(e.to_string()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|e| Error::ConfigInvalid(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::ConfigInvalid(e.to_string()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| Error::ConfigFailedToLoad {
+            source,
+            path: path.into(),
+        })?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            _ => Self::from_toml(&contents),
+        }
+    }
+
+    pub fn resolution(&self) -> Result<Resolution> {
+        match self.resolution_bits {
+            Some(bits) => Resolution::new(bits)
+                .ok_or_else(|| Error::ConfigInvalid(format!("unsupported resolution_bits {bits}"))),
+            None => Ok(Resolution::default()),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.resolution()?;
+        if let Some(baud_rate) = self.baud_rate {
+            if !ProgrammingCommand::is_supported_baud_rate(baud_rate) {
+                return Err(Error::AsyncSerialUnsupportedBaudRate(baud_rate));
+            }
+        }
+        if let Some(settings) = self.continuous_response {
+            if settings.period_micros == 0 {
+                return Err(Error::ConfigInvalid(
+                    "continuous_response.period_micros must be positive".to_owned(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn programming_commands(&self) -> Vec<ProgrammingCommand> {
+        let mut commands = Vec::new();
+        if let Some(offset) = self.position_offset {
+            commands.push(ProgrammingCommand::PositionOffsetSetting(offset));
+        }
+        if let Some(count) = self.multiturn_preset {
+            commands.push(ProgrammingCommand::MultiturnCounterSetting(count));
+        }
+        if let Some(baud_rate) = self.baud_rate {
+            commands.push(ProgrammingCommand::BaudRateSetting(baud_rate));
+        }
+        if let Some(settings) = self.continuous_response {
+            commands.push(ProgrammingCommand::ContinuousResponseSetting {
+                auto_start: settings.auto_start,
+                command: settings.command,
+                period_micros: settings.period_micros,
+            });
+        }
+        commands.push(ProgrammingCommand::ConfigurationParametersSave);
+        commands
+    }
+
+    pub fn dry_run(&self) -> Result<Vec<(ProgrammingCommand, Vec<u8>)>> {
+        let resolution = self.resolution()?;
+        Ok(self
+            .programming_commands()
+            .into_iter()
+            .map(|command| (command, command.to_bytes(resolution)))
+            .collect())
+    }
+
+    pub fn apply<T: Transport>(&self, encoder: &mut Encoder<T>) -> Result<()> {
+        self.validate()?;
+        if self.resolution_bits.is_some() && self.resolution()? != encoder.resolution() {
+            return Err(Error::ConfigInvalid(format!(
+                "resolution_bits is {} but the encoder uses {}",
+                self.resolution()?.bits(),
+                encoder.resolution().bits()
+            )));
+        }
+        for command in self.programming_commands() {
+            match command {
+                ProgrammingCommand::BaudRateSetting(baud_rate) => {
+                    encoder.change_baud_rate(baud_rate, false)?
+                }
+                ProgrammingCommand::ConfigurationParametersSave => {
+                    encoder.send_programming_command(&command)?
+                }
+                _ => encoder.send_programming_command_verified(&command)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ObservedContinuousResponse {
+    pub command: Option<Command>,
+    pub period_micros: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct CalibrationSnapshot {
+    pub counter: u8,
+    pub out_of_range: bool,
+    pub timeout: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConfigSnapshot {
+    pub serial_number: Option<String>,
+    pub host_baud_rate: Option<u32>,
+    pub counter_type: CounterType,
+    pub resolution_bits: u8,
+    pub position: Option<i16>,
+    pub multiturn_count: Option<i16>,
+    pub continuous_response: Option<ObservedContinuousResponse>,
+    pub self_calibration: Option<CalibrationSnapshot>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConfigDifference {
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl ConfigSnapshot {
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::ConfigInvalid(e.to_string()))
+    }
+
+    pub fn differences(&self, expected: &EncoderConfig) -> Vec<ConfigDifference> {
+        let mut differences = Vec::new();
+        let mut differ = |field, expected: String, actual: String| {
+            differences.push(ConfigDifference {
+                field,
+                expected,
+                actual,
+            })
+        };
+
+        if let Some(bits) = expected.resolution_bits {
+            if self.resolution_bits != bits {
+                differ(
+                    "resolution_bits",
+                    bits.to_string(),
+                    self.resolution_bits.to_string(),
+                );
+            }
+        }
+        if let Some(baud_rate) = expected.baud_rate {
+            if self.host_baud_rate != Some(baud_rate) {
+                differ(
+                    "baud_rate",
+                    baud_rate.to_string(),
+                    describe(self.host_baud_rate),
+                );
+            }
+        }
+        if expected.multiturn_preset.is_some() && self.counter_type != CounterType::MultiTurn {
+            differ(
+                "counter_type",
+                format!("{:?}", CounterType::MultiTurn),
+                format!("{:?}", self.counter_type),
+            );
+        }
+
+        match (expected.continuous_response, self.continuous_response) {
+            (Some(settings), observed) if settings.auto_start => {
+                let command = observed.and_then(|o| o.command);
+                if command.is_some() && command != Some(settings.command) {
+                    differ(
+                        "continuous_response.command",
+                        format!("{:?}", settings.command),
+                        format!("{:?}", command),
+                    );
+                }
+                let period = observed.and_then(|o| o.period_micros);
+                let expected_period = f64::from(settings.period_micros);
+                if !period.is_some_and(|p| {
+                    (f64::from(p) - expected_period).abs() <= expected_period * PERIOD_TOLERANCE
+                }) {
+                    differ(
+                        "continuous_response.period_micros",
+                        settings.period_micros.to_string(),
+                        describe(period),
+                    );
+                }
+            }
+            (Some(_), Some(observed)) => differ(
+                "continuous_response",
+                "stopped".to_owned(),
+                format!("{observed:?}"),
+            ),
+            _ => {}
+        }
+        differences
+    }
+
+    pub fn unverified(&self, expected: &EncoderConfig) -> Vec<&'static str> {
+        let mut unverified = Vec::new();
+        if expected.position_offset.is_some() {
+            unverified.push("position_offset");
+        }
+        if expected.multiturn_preset.is_some() {
+            unverified.push("multiturn_preset");
+        }
+        unverified
+    }
+}
+
+fn describe(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "unknown".to_owned(), |v| v.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{async_serial::SimulatedEncoder, CounterType};
+
+    const CONFIG: &str = r#"
+position_offset = -1200
+multiturn_preset = 3
+baud_rate = 230400
+
+[continuous_response]
+command = "position_request"
+period_micros = 5000
+"#;
+
+    #[test]
+    fn test_parse() {
+        let config = EncoderConfig::from_toml(CONFIG).unwrap();
+        assert_eq!(config.position_offset, Some(-1200));
+        assert_eq!(
+            config.continuous_response,
+            Some(ContinuousResponseSettings {
+                command: Command::PositionRequest,
+                period_micros: 5000,
+                auto_start: false,
+            })
+        );
+        assert_eq!(
+            EncoderConfig::from_toml(&config.to_toml().unwrap()).unwrap(),
+            config
+        );
+        assert_eq!(
+            EncoderConfig::from_json(&config.to_json().unwrap()).unwrap(),
+            config
+        );
+
+        assert!(matches!(
+            EncoderConfig::from_json(r#"{"baud_rate": 250000}"#),
+            Err(Error::AsyncSerialUnsupportedBaudRate(250_000))
+        ));
+        assert!(matches!(
+            EncoderConfig::from_json(r#"{"offset": 1}"#),
+            Err(Error::ConfigInvalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_dry_run() {
+        let mut config = EncoderConfig {
+            multiturn_preset: Some(-2),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.dry_run().unwrap(),
+            vec![
+                (
+                    ProgrammingCommand::MultiturnCounterSetting(-2),
+                    vec![0xCD, 0xEF, 0x89, 0xAB, b'M', 0x00, 0x00, 0xFF, 0xFE]
+                ),
+                (
+                    ProgrammingCommand::ConfigurationParametersSave,
+                    vec![0xCD, 0xEF, 0x89, 0xAB, b'c']
+                ),
+            ]
+        );
+
+        config = EncoderConfig::from_toml("resolution_bits = 12\nposition_offset = -1").unwrap();
+        assert_eq!(
+            config.dry_run().unwrap()[0].1,
+            vec![0xCD, 0xEF, 0x89, 0xAB, b'Z', 0x00, 0x00, 0x0F, 0xFF]
+        );
+        assert!(matches!(
+            EncoderConfig::from_toml("resolution_bits = 15"),
+            Err(Error::ConfigInvalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_differences() {
+        let snapshot = ConfigSnapshot {
+            serial_number: Some("ABC123".to_owned()),
+            host_baud_rate: Some(115_200),
+            counter_type: CounterType::SingleTurn,
+            resolution_bits: 14,
+            position: Some(0),
+            multiturn_count: None,
+            continuous_response: Some(ObservedContinuousResponse {
+                command: Some(Command::PositionRequest),
+                period_micros: Some(5_100),
+            }),
+            self_calibration: None,
+        };
+
+        let mut expected = EncoderConfig {
+            baud_rate: Some(115_200),
+            continuous_response: Some(ContinuousResponseSettings {
+                command: Command::PositionRequest,
+                period_micros: 5_000,
+                auto_start: true,
+            }),
+            ..Default::default()
+        };
+        assert!(snapshot.differences(&expected).is_empty());
+
+        expected.baud_rate = Some(921_600);
+        expected.position_offset = Some(-1200);
+        expected.multiturn_preset = Some(0);
+        if let Some(settings) = &mut expected.continuous_response {
+            settings.auto_start = false;
+        }
+        let fields: Vec<_> = snapshot
+            .differences(&expected)
+            .into_iter()
+            .map(|d| d.field)
+            .collect();
+        assert_eq!(fields, ["baud_rate", "counter_type", "continuous_response"]);
+        assert_eq!(
+            snapshot.unverified(&expected),
+            ["position_offset", "multiturn_preset"]
+        );
+
+        let expected = EncoderConfig {
+            resolution_bits: Some(12),
+            position_offset: Some(100),
+            ..Default::default()
+        };
+        let differences = snapshot.differences(&expected);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].field, "resolution_bits");
+        assert_eq!(differences[0].actual, "14");
+        assert!(snapshot
+            .differences(&EncoderConfig {
+                position_offset: Some(100),
+                ..Default::default()
+            })
+            .is_empty());
+    }
+
+    #[test]
+    fn test_apply() {
+        let (mut port, sim) = SimulatedEncoder::new(CounterType::MultiTurn).connect();
+        port.set_baud_rate(115_200).unwrap();
+        let mut encoder = Encoder::new(port, CounterType::MultiTurn);
+
+        EncoderConfig::from_toml(CONFIG)
+            .unwrap()
+            .apply(&mut encoder)
+            .unwrap();
+        assert_eq!(encoder.port().baud_rate(), Some(230_400));
+
+        sim.lock().power_cycle();
+        assert_eq!(sim.lock().position_offset(), -1200);
+        assert_eq!(sim.lock().baud_rate(), 230_400);
+
+        let config = EncoderConfig::from_toml("resolution_bits = 12").unwrap();
+        assert!(matches!(
+            config.apply(&mut encoder),
+            Err(Error::ConfigInvalid(_))
+        ));
+    }
+}
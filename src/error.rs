@@ -11,11 +11,162 @@ pub enum Error {
         path: PathBuf,
     },
 
+    #[error("orbis: Failed to connect: Error({:?})", .0)]
+    AsyncSerialFailedToConnect(std::io::Error),
+
+    #[error("orbis: Failed to configure: Error({:?})", .0)]
+    AsyncSerialFailedToConfigure(std::io::Error),
+
     #[error("orbis: Failed to send: Error({:?})", .0)]
     AsyncSerialFailedToSend(std::io::Error),
 
     #[error("orbis: Failed to receive: Error({:?})", .0)]
     AsyncSerialFailedToReceive(std::io::Error),
+
+    #[error(
+        "orbis: Invalid prefix: expected({:#04x}) actual({:#04x})",
+        expected,
+        actual
+    )]
+    AsyncSerialInvalidPrefix { expected: u8, actual: u8 },
+
+    #[error("orbis: Unknown prefix: actual({:#04x})", .0)]
+    AsyncSerialUnknownPrefix(u8),
+
+    #[error("orbis: Invalid length: expected({}) actual({})", expected, actual)]
+    AsyncSerialInvalidLength { expected: usize, actual: usize },
+
+    #[error("orbis: Invalid UTF-8: Error({:?})", .0)]
+    AsyncSerialInvalidUtf8(#[from] std::str::Utf8Error),
+
+    #[error(
+        "orbis: Unexpected command: expected({:?}) actual({:?})",
+        expected,
+        actual
+    )]
+    AsyncSerialUnexpectedCommand {
+        expected: crate::async_serial::Command,
+        actual: crate::async_serial::Command,
+    },
+
+    #[error("orbis: Frame not found: prefix({:#04x}) skipped({})", prefix, skipped)]
+    AsyncSerialFrameNotFound { prefix: u8, skipped: usize },
+
+    #[error("orbis: Continuous response stream closed")]
+    AsyncSerialStreamClosed,
+
+    #[error(
+        "orbis: Position offset not applied: expected({}) actual({})",
+        expected,
+        actual
+    )]
+    AsyncSerialPositionOffsetNotApplied { expected: i16, actual: i16 },
+
+    #[error(
+        "orbis: Position offset not verified: offset({}) Error({:?})",
+        offset,
+        source
+    )]
+    AsyncSerialPositionOffsetNotVerified {
+        offset: i16,
+        #[source]
+        source: Box<Error>,
+    },
+
+    #[error(
+        "orbis: Multiturn counter not applied: expected({}) actual({:?})",
+        expected,
+        actual
+    )]
+    AsyncSerialMultiturnCounterNotApplied { expected: i16, actual: Option<i16> },
+
+    #[error("orbis: Unsupported baud rate: baud_rate({})", .0)]
+    AsyncSerialUnsupportedBaudRate(u32),
+
+    #[error("orbis: Failed to list ports: Error({:?})", .0)]
+    AsyncSerialFailedToListPorts(#[source] serialport::Error),
+
+    #[error("orbis: Encoder not found: serial_number({})", .0)]
+    AsyncSerialEncoderNotFound(String),
+
+    #[error("orbis: Counter type not detected: length({})", length)]
+    AsyncSerialCounterTypeNotDetected { length: usize },
+
+    #[error("orbis: Self-calibration timed out on the device")]
+    AsyncSerialSelfCalibrationTimeout,
+
+    #[error("orbis: Self-calibration failed: signal out of range")]
+    AsyncSerialSelfCalibrationOutOfRange,
+
+    #[error("orbis: Self-calibration not finished within {:?}", .0)]
+    AsyncSerialSelfCalibrationNotFinished(std::time::Duration),
+
+    #[error("orbis: Baud rate not detected")]
+    AsyncSerialBaudRateNotDetected,
+
+    #[error(
+        "orbis: Baud rate not applied: baud_rate({}) Error({:?})",
+        baud_rate,
+        source
+    )]
+    AsyncSerialBaudRateNotApplied {
+        baud_rate: u32,
+        #[source]
+        source: Box<Error>,
+    },
+
+    #[error(
+        "orbis: Continuous response not applied: expected({:?}) actual({:?})",
+        expected,
+        actual
+    )]
+    AsyncSerialContinuousResponseNotApplied {
+        expected: std::time::Duration,
+        actual: Option<std::time::Duration>,
+    },
+
+    #[error("orbis: Ambiguous jump: previous({}) current({})", previous, current)]
+    TurnTrackerAmbiguousJump { previous: i16, current: i16 },
+
+    #[error(
+        "orbis: Resolution mismatch: expected({:?}) actual({:?})",
+        expected,
+        actual
+    )]
+    TurnTrackerResolutionMismatch {
+        expected: crate::Resolution,
+        actual: crate::Resolution,
+    },
+
+    #[error("orbis: Failed to save turns: path({:?}) Error({:?})", path, source)]
+    TurnTrackerFailedToSave {
+        #[source]
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[error("orbis: Failed to load turns: path({:?}) Error({:?})", path, source)]
+    TurnTrackerFailedToLoad {
+        #[source]
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[error("orbis: Invalid turn tracker state: {}", .0)]
+    TurnTrackerInvalidState(String),
+
+    #[error("orbis: Failed to load config: path({:?}) Error({:?})", path, source)]
+    ConfigFailedToLoad {
+        #[source]
+        source: std::io::Error,
+        path: PathBuf,
+    },
+
+    #[error("orbis: Invalid config: {}", .0)]
+    ConfigInvalid(String),
+
+    #[error("orbis: Invalid serial number: serial_number({:?})", .0)]
+    SimulatorInvalidSerialNumber(String),
 }
 
 pub type Result<T> = ::std::result::Result<T, Error>;
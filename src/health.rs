@@ -0,0 +1,279 @@
+mod monitor;
+
+use std::{collections::BTreeSet, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::async_serial::{
+    PositionAndDetailedStatus, PositionAndStatus, PositionAndTemperature, Response,
+};
+
+pub use monitor::{HealthMonitor, HealthState, HealthThresholds, HealthTransition};
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Ok,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fault {
+    GeneralError,
+    MultiturnError,
+    SignalTooHigh,
+    SignalTooLow,
+    OverTemperature,
+    CriticalTemperature,
+    Overspeed,
+    GeneralWarning,
+}
+
+impl Fault {
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::GeneralError | Self::MultiturnError | Self::CriticalTemperature => {
+                Severity::Error
+            }
+            Self::SignalTooHigh
+            | Self::SignalTooLow
+            | Self::OverTemperature
+            | Self::Overspeed
+            | Self::GeneralWarning => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::GeneralError => "general error",
+            Self::MultiturnError => "multiturn counter error",
+            Self::SignalTooHigh => "signal too high",
+            Self::SignalTooLow => "signal too low",
+            Self::OverTemperature => "over temperature",
+            Self::CriticalTemperature => "critical temperature",
+            Self::Overspeed => "overspeed",
+            Self::GeneralWarning => "general warning",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "HealthRecord", into = "HealthRecord")]
+pub struct Health {
+    faults: BTreeSet<Fault>,
+    temperature: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct HealthRecord {
+    #[serde(default)]
+    severity: Severity,
+    #[serde(default)]
+    faults: BTreeSet<Fault>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+}
+
+impl From<HealthRecord> for Health {
+    fn from(record: HealthRecord) -> Self {
+        Self {
+            faults: record.faults,
+            temperature: record.temperature,
+        }
+    }
+}
+
+impl From<Health> for HealthRecord {
+    fn from(health: Health) -> Self {
+        Self {
+            severity: health.severity(),
+            faults: health.faults,
+            temperature: health.temperature,
+        }
+    }
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_status(response: &(impl PositionAndStatus + ?Sized)) -> Self {
+        let mut health = Self::new();
+        if response.is_error() {
+            health.insert(Fault::GeneralError);
+        }
+        if response.is_warning() {
+            health.insert(Fault::GeneralWarning);
+        }
+        health
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.faults().map(Fault::severity).max().unwrap_or_default()
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.faults.is_empty()
+    }
+
+    pub fn faults(&self) -> impl Iterator<Item = Fault> + '_ {
+        self.faults.iter().copied()
+    }
+
+    pub fn contains(&self, fault: Fault) -> bool {
+        self.faults.contains(&fault)
+    }
+
+    pub fn insert(&mut self, fault: Fault) {
+        self.faults.insert(fault);
+    }
+
+    pub fn remove(&mut self, fault: Fault) {
+        self.faults.remove(&fault);
+    }
+
+    pub fn temperature(&self) -> Option<f64> {
+        self.temperature
+    }
+
+    pub fn set_temperature(&mut self, temperature: f64) {
+        self.temperature = Some(temperature);
+    }
+
+    pub fn merge(&mut self, other: &Health) {
+        self.faults.extend(other.faults());
+        if other.temperature.is_some() {
+            self.temperature = other.temperature;
+        }
+    }
+}
+
+impl From<&PositionAndDetailedStatus> for Health {
+    fn from(response: &PositionAndDetailedStatus) -> Self {
+        let mut health = Self::from_status(response);
+        for (is_set, fault) in [
+            (response.is_signal_too_high(), Fault::SignalTooHigh),
+            (response.is_signal_too_low(), Fault::SignalTooLow),
+            (
+                response.is_temperature_out_of_range(),
+                Fault::OverTemperature,
+            ),
+            (response.is_speed_too_high(), Fault::Overspeed),
+            (response.is_multiturn_counter_error(), Fault::MultiturnError),
+        ] {
+            if is_set {
+                health.insert(fault);
+            }
+        }
+        health
+    }
+}
+
+impl From<&PositionAndTemperature> for Health {
+    fn from(response: &PositionAndTemperature) -> Self {
+        let mut health = Self::from_status(response);
+        health.set_temperature(response.temperature());
+        health
+    }
+}
+
+impl From<&Response> for Health {
+    fn from(response: &Response) -> Self {
+        match response {
+            Response::PositionAndDetailedStatus(r) => r.into(),
+            Response::PositionAndTemperature(r) => r.into(),
+            response => response
+                .position_and_status()
+                .map(Self::from_status)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl fmt::Display for Health {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.severity())?;
+        for (i, fault) in self.faults().enumerate() {
+            write!(f, "{}{fault}", if i == 0 { ": " } else { ", " })?;
+        }
+        if let Some(temperature) = self.temperature {
+            write!(f, " ({temperature:.1} °C)")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CounterType;
+
+    #[test]
+    fn test_detailed_status() {
+        let response = PositionAndDetailedStatus::from_bytes(
+            CounterType::SingleTurn,
+            &[b'd', 0x40, 0x01, 0x50],
+        )
+        .unwrap();
+        let health = Health::from(&response);
+
+        assert_eq!(
+            health.faults().collect::<Vec<_>>(),
+            [Fault::GeneralError, Fault::SignalTooLow, Fault::Overspeed]
+        );
+        assert_eq!(health.severity(), Severity::Error);
+        assert!(!health.is_ok());
+        assert_eq!(
+            health.to_string(),
+            "error: general error, signal too low, overspeed"
+        );
+    }
+
+    #[test]
+    fn test_temperature() {
+        let response = PositionAndTemperature::from_bytes(
+            CounterType::SingleTurn,
+            &[b't', 0x40, 0x02, 0x01, 0x3B],
+        )
+        .unwrap();
+        let mut health = Health::from(&Response::from(response));
+        assert_eq!(health.severity(), Severity::Warning);
+        assert_eq!(health.to_string(), "warning: general warning (31.5 °C)");
+
+        health.remove(Fault::GeneralWarning);
+        assert!(health.is_ok());
+        assert_eq!(health.to_string(), "ok (31.5 °C)");
+    }
+
+    #[test]
+    fn test_serde() {
+        let mut health = Health::new();
+        health.insert(Fault::OverTemperature);
+        health.set_temperature(90.0);
+
+        let json = serde_json::to_string(&health).unwrap();
+        assert_eq!(
+            json,
+            r#"{"severity":"warning","faults":["over_temperature"],"temperature":90.0}"#
+        );
+        assert_eq!(serde_json::from_str::<Health>(&json).unwrap(), health);
+    }
+}
@@ -0,0 +1,361 @@
+use std::{fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Fault, Health, Severity};
+use crate::{
+    async_serial::{Encoder, Response, Sample, Transport},
+    error::{Error, Result},
+};
+
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthState {
+    #[default]
+    Ok,
+    Degraded,
+    Faulted,
+    Lost,
+}
+
+impl fmt::Display for HealthState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Ok => "ok",
+            Self::Degraded => "degraded",
+            Self::Faulted => "faulted",
+            Self::Lost => "lost",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HealthThresholds {
+    pub warning_temperature: Option<f64>,
+    pub fault_temperature: Option<f64>,
+    pub debounce_samples: u32,
+    pub lost_after_timeouts: u32,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            warning_temperature: None,
+            fault_temperature: None,
+            debounce_samples: 3,
+            lost_after_timeouts: 3,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthTransition {
+    pub from: HealthState,
+    pub to: HealthState,
+    pub health: Health,
+}
+
+type TransitionCallback = Box<dyn FnMut(&HealthTransition) + Send>;
+
+pub struct HealthMonitor {
+    thresholds: HealthThresholds,
+    state: HealthState,
+    health: Health,
+    pending: Option<(HealthState, u32)>,
+    consecutive_timeouts: u32,
+    callbacks: Vec<TransitionCallback>,
+}
+
+impl HealthMonitor {
+    pub fn new(thresholds: HealthThresholds) -> Self {
+        Self {
+            thresholds,
+            state: HealthState::Ok,
+            health: Health::new(),
+            pending: None,
+            consecutive_timeouts: 0,
+            callbacks: Vec::new(),
+        }
+    }
+
+    pub fn thresholds(&self) -> HealthThresholds {
+        self.thresholds
+    }
+
+    pub fn state(&self) -> HealthState {
+        self.state
+    }
+
+    pub fn health(&self) -> &Health {
+        &self.health
+    }
+
+    pub fn consecutive_timeouts(&self) -> u32 {
+        self.consecutive_timeouts
+    }
+
+    pub fn on_transition(&mut self, callback: impl FnMut(&HealthTransition) + Send + 'static) {
+        self.callbacks.push(Box::new(callback));
+    }
+
+    pub fn update(&mut self, mut health: Health) -> HealthState {
+        self.consecutive_timeouts = 0;
+        let severity = self.apply_thresholds(&mut health);
+        self.health = health;
+
+        let target = match severity {
+            Severity::Ok => HealthState::Ok,
+            Severity::Warning => HealthState::Degraded,
+            Severity::Error => HealthState::Faulted,
+        };
+        if target == self.state {
+            self.pending = None;
+        } else if target == HealthState::Faulted || self.state == HealthState::Lost {
+            self.transition(target);
+        } else {
+            let count = match self.pending {
+                Some((pending, count)) if pending == target => count + 1,
+                _ => 1,
+            };
+            if count >= self.thresholds.debounce_samples {
+                self.transition(target);
+            } else {
+                self.pending = Some((target, count));
+            }
+        }
+        self.state
+    }
+
+    pub fn update_response(&mut self, response: &Response) -> HealthState {
+        self.update(response.into())
+    }
+
+    pub fn update_sample<R: Into<Response>>(
+        &mut self,
+        sample: Result<Sample<R>>,
+    ) -> Result<HealthState> {
+        match sample {
+            Ok(sample) => Ok(self.update_response(&sample.response.into())),
+            Err(e) if is_link_failure(&e) => Ok(self.record_timeout()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn record_timeout(&mut self) -> HealthState {
+        self.consecutive_timeouts += 1;
+        if self.consecutive_timeouts >= self.thresholds.lost_after_timeouts {
+            self.transition(HealthState::Lost);
+        }
+        self.state
+    }
+
+    pub fn poll<T: Transport>(&mut self, encoder: &mut Encoder<T>) -> Result<HealthState> {
+        let detailed = match encoder.read_detailed_status() {
+            Ok(response) => Health::from(&response),
+            Err(e) if is_link_failure(&e) => return Ok(self.record_timeout()),
+            Err(e) => return Err(e),
+        };
+        let temperature = match encoder.read_temperature() {
+            Ok(response) => Health::from(&response),
+            Err(e) if is_link_failure(&e) => {
+                self.update(detailed);
+                return Ok(self.record_timeout());
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut health = detailed;
+        health.merge(&temperature);
+        Ok(self.update(health))
+    }
+
+    pub fn run<T: Transport>(
+        &mut self,
+        encoder: &mut Encoder<T>,
+        interval: Duration,
+        mut keep_running: impl FnMut(&Self) -> bool,
+    ) -> Result<()> {
+        while keep_running(self) {
+            self.poll(encoder)?;
+            std::thread::sleep(interval);
+        }
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        self.state = HealthState::Ok;
+        self.health = Health::new();
+        self.pending = None;
+        self.consecutive_timeouts = 0;
+    }
+
+    fn apply_thresholds(&self, health: &mut Health) -> Severity {
+        if let Some(temperature) = health.temperature() {
+            let exceeds = |limit: Option<f64>| limit.is_some_and(|limit| temperature >= limit);
+            if exceeds(self.thresholds.fault_temperature) {
+                health.insert(Fault::CriticalTemperature);
+            } else if exceeds(self.thresholds.warning_temperature) {
+                health.insert(Fault::OverTemperature);
+            }
+        }
+        health.severity()
+    }
+
+    fn transition(&mut self, to: HealthState) {
+        self.pending = None;
+        if to == self.state {
+            return;
+        }
+        let transition = HealthTransition {
+            from: self.state,
+            to,
+            health: self.health.clone(),
+        };
+        self.state = to;
+        for callback in &mut self.callbacks {
+            callback(&transition);
+        }
+    }
+}
+
+fn is_link_failure(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::AsyncSerialFailedToReceive(_) | Error::AsyncSerialFrameNotFound { .. }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::{async_serial::SimulatedEncoder, CounterType};
+
+    fn warning() -> Health {
+        let mut health = Health::new();
+        health.insert(Fault::GeneralWarning);
+        health
+    }
+
+    fn error() -> Health {
+        let mut health = Health::new();
+        health.insert(Fault::SignalTooLow);
+        health.insert(Fault::GeneralError);
+        health
+    }
+
+    #[test]
+    fn test_debounce() {
+        let mut monitor = HealthMonitor::new(HealthThresholds::default());
+        let transitions = Arc::new(Mutex::new(Vec::new()));
+        {
+            let transitions = transitions.clone();
+            monitor.on_transition(move |t| transitions.lock().unwrap().push((t.from, t.to)));
+        }
+
+        assert_eq!(monitor.update(warning()), HealthState::Ok);
+        assert_eq!(monitor.update(Health::new()), HealthState::Ok);
+        assert_eq!(monitor.update(warning()), HealthState::Ok);
+        assert_eq!(monitor.update(warning()), HealthState::Ok);
+        assert_eq!(monitor.update(warning()), HealthState::Degraded);
+
+        assert_eq!(monitor.update(error()), HealthState::Faulted);
+        assert_eq!(monitor.update(Health::new()), HealthState::Faulted);
+        assert_eq!(monitor.update(Health::new()), HealthState::Faulted);
+        assert_eq!(monitor.update(Health::new()), HealthState::Ok);
+
+        assert_eq!(
+            *transitions.lock().unwrap(),
+            [
+                (HealthState::Ok, HealthState::Degraded),
+                (HealthState::Degraded, HealthState::Faulted),
+                (HealthState::Faulted, HealthState::Ok),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_temperature_thresholds() {
+        let mut monitor = HealthMonitor::new(HealthThresholds {
+            warning_temperature: Some(70.0),
+            fault_temperature: Some(85.0),
+            debounce_samples: 1,
+            ..Default::default()
+        });
+        let transitions = Arc::new(Mutex::new(Vec::new()));
+        {
+            let transitions = transitions.clone();
+            monitor.on_transition(move |t| transitions.lock().unwrap().push(t.clone()));
+        }
+        let sample = |temperature| {
+            let mut health = Health::new();
+            health.set_temperature(temperature);
+            health
+        };
+
+        assert_eq!(monitor.update(sample(40.0)), HealthState::Ok);
+        assert_eq!(monitor.update(sample(72.5)), HealthState::Degraded);
+        assert!(monitor.health().contains(Fault::OverTemperature));
+        assert_eq!(monitor.update(sample(90.0)), HealthState::Faulted);
+
+        let transitions = transitions.lock().unwrap();
+        let faulted = &transitions.last().unwrap().health;
+        assert_eq!(faulted.severity(), Severity::Error);
+        assert_eq!(faulted.to_string(), "error: critical temperature (90.0 °C)");
+    }
+
+    #[test]
+    fn test_poll() {
+        let mut sim = SimulatedEncoder::new(CounterType::SingleTurn);
+        sim.set_temperature(50.0);
+        let (mut port, sim) = sim.connect();
+        port.set_timeout(Duration::from_millis(50)).unwrap();
+        let mut encoder = Encoder::new(port, CounterType::SingleTurn);
+        let mut monitor = HealthMonitor::new(HealthThresholds {
+            fault_temperature: Some(80.0),
+            ..Default::default()
+        });
+
+        assert_eq!(monitor.poll(&mut encoder).unwrap(), HealthState::Ok);
+        assert_eq!(monitor.health().temperature(), Some(50.0));
+
+        sim.lock().set_detailed_status(0b10000000);
+        sim.lock().set_error(true);
+        assert_eq!(monitor.poll(&mut encoder).unwrap(), HealthState::Faulted);
+        assert!(monitor.health().contains(Fault::SignalTooHigh));
+
+        sim.lock().queue_noise(&[0; 256]);
+        assert_eq!(monitor.poll(&mut encoder).unwrap(), HealthState::Faulted);
+        assert_eq!(monitor.consecutive_timeouts(), 1);
+    }
+
+    #[test]
+    fn test_lost() {
+        let mut monitor = HealthMonitor::new(HealthThresholds {
+            lost_after_timeouts: 2,
+            ..Default::default()
+        });
+        let timeout = || -> Result<Sample<Response>> {
+            Err(Error::AsyncSerialFailedToReceive(
+                std::io::ErrorKind::TimedOut.into(),
+            ))
+        };
+
+        let garbled = || -> Result<Sample<Response>> {
+            Err(Error::AsyncSerialFrameNotFound {
+                prefix: b'1',
+                skipped: 256,
+            })
+        };
+
+        assert_eq!(monitor.update_sample(timeout()).unwrap(), HealthState::Ok);
+        assert_eq!(monitor.update_sample(garbled()).unwrap(), HealthState::Lost);
+        assert_eq!(monitor.consecutive_timeouts(), 2);
+
+        assert_eq!(monitor.update(Health::new()), HealthState::Ok);
+        assert_eq!(monitor.consecutive_timeouts(), 0);
+    }
+}
@@ -2,11 +2,25 @@
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![allow(clippy::unusual_byte_groupings)]
 
-const BITS_PER_REVOLUTION: usize = 14;
-const COUNTS_PER_REVOLUTION: usize = 2 << (BITS_PER_REVOLUTION - 1);
-
+mod absolute_position;
 pub mod async_serial;
+mod config;
 mod counter_type;
 pub mod error;
+mod health;
+mod resolution;
+mod turn_tracker;
+mod velocity;
 
+pub use absolute_position::{AbsolutePosition, AbsolutePositionTracker};
+pub use config::{
+    CalibrationSnapshot, ConfigDifference, ConfigSnapshot, ContinuousResponseSettings,
+    EncoderConfig, ObservedContinuousResponse,
+};
 pub use counter_type::CounterType;
+pub use health::{
+    Fault, Health, HealthMonitor, HealthState, HealthThresholds, HealthTransition, Severity,
+};
+pub use resolution::Resolution;
+pub use turn_tracker::TurnTracker;
+pub use velocity::{VelocityEstimate, VelocityEstimator, VelocityFilter};
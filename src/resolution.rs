@@ -0,0 +1,54 @@
+const POSITION_DATA_BITS: u8 = 16;
+const STATUS_BITS: u8 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resolution {
+    bits: u8,
+}
+
+impl Resolution {
+    pub const BITS_12: Self = Self { bits: 12 };
+    pub const BITS_14: Self = Self { bits: 14 };
+
+    pub fn new(bits: u8) -> Option<Self> {
+        if (1..=POSITION_DATA_BITS - STATUS_BITS).contains(&bits) {
+            Some(Self { bits })
+        } else {
+            None
+        }
+    }
+
+    pub fn bits(self) -> u8 {
+        self.bits
+    }
+
+    pub fn counts_per_revolution(self) -> u32 {
+        1 << self.bits
+    }
+
+    pub(crate) fn position_shift(self) -> u8 {
+        POSITION_DATA_BITS - self.bits
+    }
+}
+
+impl Default for Resolution {
+    fn default() -> Self {
+        Self::BITS_14
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolution() {
+        assert_eq!(Resolution::default().counts_per_revolution(), 16384);
+        assert_eq!(Resolution::BITS_12.counts_per_revolution(), 4096);
+        assert_eq!(Resolution::BITS_14.position_shift(), 2);
+        assert_eq!(Resolution::BITS_12.position_shift(), 4);
+        assert_eq!(Resolution::new(12), Some(Resolution::BITS_12));
+        assert_eq!(Resolution::new(0), None);
+        assert_eq!(Resolution::new(15), None);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{f64::consts::PI, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    async_serial::PositionAndStatus,
+    error::{Error, Result},
+    Resolution,
+};
+
+pub(crate) fn wrapping_delta(previous: i64, current: i64, modulus: i64) -> i64 {
+    let half = modulus / 2;
+    (current - previous + half).rem_euclid(modulus) - half
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TurnTrackerState {
+    bits: u8,
+    turns: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<i16>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnTracker {
+    resolution: Resolution,
+    max_step: u32,
+    turns: i64,
+    position: Option<i16>,
+}
+
+impl TurnTracker {
+    pub fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            max_step: resolution.counts_per_revolution() / 2,
+            turns: 0,
+            position: None,
+        }
+    }
+
+    pub fn with_state(resolution: Resolution, turns: i64, position: i16) -> Self {
+        Self {
+            turns,
+            position: Some(position),
+            ..Self::new(resolution)
+        }
+    }
+
+    pub fn set_max_step(&mut self, counts: u32) {
+        self.max_step = counts.min(self.resolution.counts_per_revolution() / 2);
+    }
+
+    pub fn max_step(&self) -> u32 {
+        self.max_step
+    }
+
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    pub fn turns(&self) -> i64 {
+        self.turns
+    }
+
+    pub fn position(&self) -> Option<i16> {
+        self.position
+    }
+
+    pub fn total_counts(&self) -> i64 {
+        self.turns * self.counts() + self.position.map_or(0, i64::from)
+    }
+
+    pub fn angle_rad(&self) -> f64 {
+        2.0 * PI * self.total_counts() as f64 / self.counts() as f64
+    }
+
+    fn counts(&self) -> i64 {
+        self.resolution.counts_per_revolution() as i64
+    }
+
+    pub fn update(&mut self, sample: &(impl PositionAndStatus + ?Sized)) -> Result<f64> {
+        if sample.resolution() != self.resolution {
+            return Err(Error::TurnTrackerResolutionMismatch {
+                expected: self.resolution,
+                actual: sample.resolution(),
+            });
+        }
+        self.update_position(sample.position())
+    }
+
+    pub fn update_position(&mut self, position: i16) -> Result<f64> {
+        if let Some(previous) = self.position {
+            let delta = wrapping_delta(previous.into(), position.into(), self.counts());
+            if delta.unsigned_abs() > self.max_step as u64 || delta == -self.counts() / 2 {
+                return Err(Error::TurnTrackerAmbiguousJump {
+                    previous,
+                    current: position,
+                });
+            }
+        }
+        Ok(self.force_update_position(position))
+    }
+
+    pub fn force_update_position(&mut self, position: i16) -> f64 {
+        if let Some(previous) = self.position {
+            let delta = wrapping_delta(previous.into(), position.into(), self.counts());
+            self.turns += (i64::from(previous) + delta - i64::from(position)) / self.counts();
+        }
+        self.position = Some(position);
+        self.angle_rad()
+    }
+
+    pub fn reset(&mut self) {
+        self.turns = 0;
+        self.position = None;
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = toml::to_string(&TurnTrackerState {
+            bits: self.resolution.bits(),
+            turns: self.turns,
+            position: self.position,
+        })
+        .map_err(|e| Error::TurnTrackerInvalidState(e.to_string()))?;
+
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, contents)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|source| Error::TurnTrackerFailedToSave {
+                source,
+                path: path.into(),
+            })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents =
+            fs::read_to_string(path).map_err(|source| Error::TurnTrackerFailedToLoad {
+                source,
+                path: path.into(),
+            })?;
+        let state: TurnTrackerState =
+            toml::from_str(&contents).map_err(|e| Error::TurnTrackerInvalidState(e.to_string()))?;
+
+        let resolution = Resolution::new(state.bits).ok_or_else(|| {
+            Error::TurnTrackerInvalidState(format!("unsupported bits {}", state.bits))
+        })?;
+        Ok(Self {
+            turns: state.turns,
+            position: state.position,
+            ..Self::new(resolution)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+    use crate::async_serial::MockPositionAndStatus;
+
+    #[test]
+    fn test_wrapping_delta() {
+        assert_eq!(wrapping_delta(100, 150, 16384), 50);
+        assert_eq!(wrapping_delta(8000, -8000, 16384), 384);
+        assert_eq!(wrapping_delta(-8000, 8000, 16384), -384);
+        assert_eq!(wrapping_delta(i16::MAX.into(), i16::MIN.into(), 65536), 1);
+    }
+
+    #[test]
+    fn test_unwrap() {
+        let mut tracker = TurnTracker::new(Resolution::default());
+        assert_approx_eq!(tracker.update_position(0).unwrap(), 0.0);
+
+        for position in [4000, 8000, -8000, -4000, 0, 4000, 8000, -8000] {
+            tracker.update_position(position).unwrap();
+        }
+        assert_eq!(tracker.turns(), 2);
+        assert_eq!(tracker.total_counts(), 2 * 16384 - 8000);
+
+        for position in [8000, 4000, 0, -4000, -8000, 8000] {
+            tracker.update_position(position).unwrap();
+        }
+        assert_eq!(tracker.turns(), 0);
+        assert_approx_eq!(tracker.angle_rad(), 2.0 * PI * 8000.0 / 16384.0);
+    }
+
+    #[test]
+    fn test_update_checks_resolution() {
+        let mut tracker = TurnTracker::new(Resolution::BITS_12);
+        let sample = MockPositionAndStatus {
+            position: 1500,
+            resolution: Resolution::BITS_12,
+            ..Default::default()
+        };
+        tracker.update(&sample).unwrap();
+        assert_eq!(tracker.position(), Some(1500));
+
+        assert!(matches!(
+            tracker.update(&MockPositionAndStatus {
+                position: 6000,
+                ..Default::default()
+            }),
+            Err(Error::TurnTrackerResolutionMismatch {
+                expected: Resolution::BITS_12,
+                actual: Resolution::BITS_14
+            })
+        ));
+        assert_eq!(tracker.position(), Some(1500));
+    }
+
+    #[test]
+    fn test_ambiguous_jump() {
+        let mut tracker = TurnTracker::new(Resolution::BITS_12);
+        assert_eq!(tracker.max_step(), 2048);
+        tracker.update_position(0).unwrap();
+        tracker.update_position(1500).unwrap();
+        tracker.update_position(-549).unwrap();
+        assert_eq!(tracker.turns(), 1);
+        assert!(matches!(
+            tracker.update_position(1499),
+            Err(Error::TurnTrackerAmbiguousJump {
+                previous: -549,
+                current: 1499
+            })
+        ));
+
+        tracker.reset();
+        tracker.set_max_step(1024);
+        tracker.update_position(0).unwrap();
+
+        assert!(matches!(
+            tracker.update_position(2000),
+            Err(Error::TurnTrackerAmbiguousJump {
+                previous: 0,
+                current: 2000
+            })
+        ));
+        assert_eq!(tracker.position(), Some(0));
+
+        tracker.set_max_step(2048);
+        tracker.update_position(2000).unwrap();
+        tracker.update_position(-2000).unwrap();
+        assert_eq!(tracker.turns(), 1);
+    }
+
+    #[test]
+    fn test_persistence() {
+        let path = std::env::temp_dir().join(format!("orbis_turns_{}.toml", std::process::id()));
+
+        let tracker = TurnTracker::with_state(Resolution::BITS_12, -3, 1500);
+        tracker.save(&path).unwrap();
+        let mut loaded = TurnTracker::load(&path).unwrap();
+        assert_eq!(loaded, tracker);
+
+        loaded.update_position(-1900).unwrap();
+        assert_eq!(loaded.turns(), -2);
+
+        fs::write(&path, "bits=12\nturns=x\n").unwrap();
+        assert!(matches!(
+            TurnTracker::load(&path),
+            Err(Error::TurnTrackerInvalidState(_))
+        ));
+        fs::write(&path, "bits = 15\nturns = 0\n").unwrap();
+        assert!(matches!(
+            TurnTracker::load(&path),
+            Err(Error::TurnTrackerInvalidState(_))
+        ));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            TurnTracker::load(&path),
+            Err(Error::TurnTrackerFailedToLoad { .. })
+        ));
+    }
+}
@@ -0,0 +1,313 @@
+use std::{collections::VecDeque, f64::consts::PI, time::Instant};
+
+use crate::{
+    async_serial::{PositionAndStatus, Sample},
+    AbsolutePositionTracker,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VelocityFilter {
+    FiniteDifference,
+    Regression { window: usize },
+    AlphaBeta { alpha: f64, beta: f64 },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VelocityEstimate {
+    angle_rad: f64,
+    rad_per_sec: f64,
+    rad_per_sec2: f64,
+}
+
+impl VelocityEstimate {
+    pub fn angle_rad(&self) -> f64 {
+        self.angle_rad
+    }
+
+    pub fn rad_per_sec(&self) -> f64 {
+        self.rad_per_sec
+    }
+
+    pub fn rpm(&self) -> f64 {
+        self.rad_per_sec * 60.0 / (2.0 * PI)
+    }
+
+    pub fn rad_per_sec2(&self) -> f64 {
+        self.rad_per_sec2
+    }
+
+    pub fn rpm_per_sec(&self) -> f64 {
+        self.rad_per_sec2 * 60.0 / (2.0 * PI)
+    }
+}
+
+pub struct VelocityEstimator {
+    filter: VelocityFilter,
+    origin: Option<Instant>,
+    tracker: AbsolutePositionTracker,
+    history: VecDeque<(f64, f64)>,
+    estimate: Option<VelocityEstimate>,
+}
+
+impl VelocityEstimator {
+    pub fn new(filter: VelocityFilter) -> Self {
+        Self {
+            filter,
+            origin: None,
+            tracker: AbsolutePositionTracker::new(),
+            history: VecDeque::new(),
+            estimate: None,
+        }
+    }
+
+    pub fn filter(&self) -> VelocityFilter {
+        self.filter
+    }
+
+    pub fn estimate(&self) -> Option<VelocityEstimate> {
+        self.estimate
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.filter);
+    }
+
+    pub fn update_sample<R: PositionAndStatus>(
+        &mut self,
+        sample: &Sample<R>,
+    ) -> Option<VelocityEstimate> {
+        self.update(&sample.response, sample.timestamp)
+    }
+
+    pub fn update(
+        &mut self,
+        response: &impl PositionAndStatus,
+        timestamp: Instant,
+    ) -> Option<VelocityEstimate> {
+        let angle = match self.tracker.update(response) {
+            Ok(position) => position.angle_rad(),
+            Err(_) => {
+                self.reset();
+                self.tracker.force_update(response).angle_rad()
+            }
+        };
+        let origin = *self.origin.get_or_insert(timestamp);
+        let time = timestamp.saturating_duration_since(origin).as_secs_f64();
+
+        if let Some(&(last_time, _)) = self.history.back() {
+            if time <= last_time {
+                return self.estimate;
+            }
+        }
+        self.history.push_back((time, angle));
+
+        let estimate = match self.filter {
+            VelocityFilter::FiniteDifference => self.finite_difference(),
+            VelocityFilter::Regression { window } => self.regression(window.max(2)),
+            VelocityFilter::AlphaBeta { alpha, beta } => self.alpha_beta(alpha, beta),
+        };
+        if estimate.is_some() {
+            self.estimate = estimate;
+        }
+        estimate
+    }
+
+    fn finite_difference(&mut self) -> Option<VelocityEstimate> {
+        while self.history.len() > 2 {
+            self.history.pop_front();
+        }
+        if self.history.len() < 2 {
+            return None;
+        }
+        let (t0, a0) = self.history[0];
+        let (t1, a1) = self.history[1];
+
+        let rad_per_sec = (a1 - a0) / (t1 - t0);
+        Some(VelocityEstimate {
+            angle_rad: a1,
+            rad_per_sec,
+            rad_per_sec2: self.acceleration(rad_per_sec, t1 - t0),
+        })
+    }
+
+    fn regression(&mut self, window: usize) -> Option<VelocityEstimate> {
+        while self.history.len() > window {
+            self.history.pop_front();
+        }
+        let n = self.history.len();
+        if n < 2 {
+            return None;
+        }
+
+        let &(t_first, _) = self.history.front()?;
+        let &(t_last, a_last) = self.history.back()?;
+        let span = t_last - t_first;
+        let mut s = [0.0; 5];
+        let mut r = [0.0; 3];
+        for &(t, a) in &self.history {
+            let (t, a) = ((t - t_last) / span, a - a_last);
+            let mut tk = 1.0;
+            for (k, s) in s.iter_mut().enumerate() {
+                *s += tk;
+                if k < 3 {
+                    r[k] += tk * a;
+                }
+                tk *= t;
+            }
+        }
+
+        let quadratic = if n >= 3 {
+            let m = [[s[0], s[1], s[2]], [s[1], s[2], s[3]], [s[2], s[3], s[4]]];
+            let det = determinant(m);
+            (det.abs() > f64::EPSILON).then(|| {
+                let solve = |column: usize| {
+                    let mut m = m;
+                    for (row, &r) in m.iter_mut().zip(&r) {
+                        row[column] = r;
+                    }
+                    determinant(m) / det
+                };
+                (solve(0), solve(1), solve(2))
+            })
+        } else {
+            None
+        };
+
+        let (intercept, rad_per_sec, rad_per_sec2) = quadratic.unwrap_or_else(|| {
+            let slope = (s[0] * r[1] - s[1] * r[0]) / (s[0] * s[2] - s[1] * s[1]);
+            ((r[0] - slope * s[1]) / s[0], slope, 0.0)
+        });
+        Some(VelocityEstimate {
+            angle_rad: a_last + intercept,
+            rad_per_sec: rad_per_sec / span,
+            rad_per_sec2: 2.0 * rad_per_sec2 / (span * span),
+        })
+    }
+
+    fn alpha_beta(&mut self, alpha: f64, beta: f64) -> Option<VelocityEstimate> {
+        let (time, angle) = self.history.pop_back()?;
+        let previous = self.history.pop_back();
+        self.history.clear();
+        self.history.push_back((time, angle));
+
+        let Some((last_time, _)) = previous else {
+            self.estimate = Some(VelocityEstimate {
+                angle_rad: angle,
+                ..Default::default()
+            });
+            return None;
+        };
+        let state = self.estimate.unwrap_or_default();
+
+        let dt = time - last_time;
+        let predicted = state.angle_rad + state.rad_per_sec * dt;
+        let residual = angle - predicted;
+        let rad_per_sec = state.rad_per_sec + beta / dt * residual;
+        Some(VelocityEstimate {
+            angle_rad: predicted + alpha * residual,
+            rad_per_sec,
+            rad_per_sec2: (rad_per_sec - state.rad_per_sec) / dt,
+        })
+    }
+
+    fn acceleration(&self, rad_per_sec: f64, dt: f64) -> f64 {
+        self.estimate
+            .map_or(0.0, |previous| (rad_per_sec - previous.rad_per_sec) / dt)
+    }
+}
+
+fn determinant(m: [[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use assert_approx_eq::assert_approx_eq;
+
+    use super::*;
+    use crate::{async_serial::MockPositionAndStatus, Resolution};
+
+    fn raw(counts: i64, multiturn: bool) -> MockPositionAndStatus {
+        let cpr = Resolution::default().counts_per_revolution() as i64;
+        let half = cpr / 2;
+        let turns = (counts + half).div_euclid(cpr);
+        MockPositionAndStatus {
+            multiturn_count: multiturn.then_some(turns as i16),
+            position: (counts - turns * cpr) as i16,
+            ..Default::default()
+        }
+    }
+
+    fn run(filter: VelocityFilter, start: i64, multiturn: bool, rpm: f64) -> VelocityEstimate {
+        let mut estimator = VelocityEstimator::new(filter);
+        let origin = Instant::now();
+        let counts_per_sec = rpm / 60.0 * Resolution::default().counts_per_revolution() as f64;
+        let mut estimate = None;
+        for i in 0..200 {
+            let t = i as f64 * 0.01;
+            let counts = start + (counts_per_sec * t).round() as i64;
+            estimate =
+                estimator.update(&raw(counts, multiturn), origin + Duration::from_secs_f64(t));
+        }
+        estimate.unwrap()
+    }
+
+    #[test]
+    fn test_finite_difference() {
+        let estimate = run(VelocityFilter::FiniteDifference, 0, false, 120.0);
+        assert_approx_eq!(estimate.rpm(), 120.0, 0.5);
+        assert_approx_eq!(estimate.rpm_per_sec(), 0.0, 100.0);
+        assert_approx_eq!(estimate.angle_rad(), 2.0 * PI * 2.0 * 1.99, 1e-3);
+    }
+
+    #[test]
+    fn test_regression() {
+        let estimate = run(VelocityFilter::Regression { window: 10 }, 0, false, -90.0);
+        assert_approx_eq!(estimate.rpm(), -90.0, 0.1);
+        assert_approx_eq!(estimate.rad_per_sec2(), 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_alpha_beta() {
+        let estimate = run(
+            VelocityFilter::AlphaBeta {
+                alpha: 0.5,
+                beta: 0.1,
+            },
+            0,
+            false,
+            300.0,
+        );
+        assert_approx_eq!(estimate.rad_per_sec(), 10.0 * PI, 0.1);
+    }
+
+    #[test]
+    fn test_multiturn_rollover() {
+        let cpr = Resolution::default().counts_per_revolution() as i64;
+        let estimate = run(
+            VelocityFilter::Regression { window: 5 },
+            i64::from(i16::MAX) * cpr,
+            true,
+            600.0,
+        );
+        assert_approx_eq!(estimate.rpm(), 600.0, 0.5);
+    }
+
+    #[test]
+    fn test_ignores_stale_timestamp() {
+        let mut estimator = VelocityEstimator::new(VelocityFilter::FiniteDifference);
+        let t = Instant::now();
+        assert!(estimator.update(&raw(0, false), t).is_none());
+        let estimate = estimator
+            .update(&raw(100, false), t + Duration::from_millis(10))
+            .unwrap();
+        assert_eq!(
+            estimator.update(&raw(200, false), t + Duration::from_millis(10)),
+            Some(estimate)
+        );
+    }
+}